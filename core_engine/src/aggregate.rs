@@ -0,0 +1,311 @@
+use crate::error::{Result, RustoraError};
+use polars::prelude::{AnyValue, DataFrame};
+use serde::{Deserialize, Serialize};
+
+/// Aggregate functions accepted by [`AggSpec`]. This is the allowlist: anything
+/// that cannot be parsed into one of these variants is rejected before SQL is built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AggFunction {
+    /// `COUNT(*)` when no column is given, otherwise `COUNT(column)` (non-null values).
+    Count,
+    CountDistinct,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl AggFunction {
+    /// Parse a function name as used by the bindings (`"count"`, `"avg"`, `"count_distinct"`, ...).
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "count" => Ok(Self::Count),
+            "count_distinct" => Ok(Self::CountDistinct),
+            "sum" => Ok(Self::Sum),
+            "avg" | "mean" => Ok(Self::Avg),
+            "min" => Ok(Self::Min),
+            "max" => Ok(Self::Max),
+            other => Err(RustoraError::Session(format!(
+                "Unsupported aggregate function: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// One aggregate to compute per group, e.g. `Avg` of `score` as `avg_score`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggSpec {
+    pub function: AggFunction,
+    /// Column to aggregate. Only `Count` may omit it.
+    pub column: Option<String>,
+    /// Output name; must be non-empty and unique within the request.
+    pub alias: String,
+}
+
+/// A single typed cell of a [`GroupRow`].
+///
+/// Sums over integer columns come back as `Int`, or as `Float` when the sum does
+/// not fit in an `i64`; averages are `Float`. Values of types without a dedicated
+/// variant (dates, decimals, ...) are rendered as `Text`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum GroupValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl From<AnyValue<'_>> for GroupValue {
+    fn from(value: AnyValue<'_>) -> Self {
+        match value {
+            AnyValue::Null => Self::Null,
+            AnyValue::Boolean(b) => Self::Bool(b),
+            AnyValue::Int8(v) => Self::Int(v as i64),
+            AnyValue::Int16(v) => Self::Int(v as i64),
+            AnyValue::Int32(v) => Self::Int(v as i64),
+            AnyValue::Int64(v) => Self::Int(v),
+            AnyValue::UInt8(v) => Self::Int(v as i64),
+            AnyValue::UInt16(v) => Self::Int(v as i64),
+            AnyValue::UInt32(v) => Self::Int(v as i64),
+            AnyValue::UInt64(v) => i64::try_from(v).map_or(Self::Float(v as f64), Self::Int),
+            AnyValue::Float32(v) => Self::Float(v as f64),
+            AnyValue::Float64(v) => Self::Float(v),
+            AnyValue::String(s) => Self::Text(s.to_string()),
+            AnyValue::StringOwned(s) => Self::Text(s.to_string()),
+            other => Self::Text(other.to_string()),
+        }
+    }
+}
+
+/// One result group: `keys` follow the requested group columns and `values`
+/// follow the requested [`AggSpec`]s, both in order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GroupRow {
+    pub keys: Vec<GroupValue>,
+    pub values: Vec<GroupValue>,
+}
+
+/// A built group-aggregate statement plus what its result decoder needs to know.
+pub(crate) struct GroupAggregateQuery {
+    pub sql: String,
+    /// One flag per [`AggSpec`]: the column is an integer sum emitted as decimal text.
+    pub integer_sums: Vec<bool>,
+}
+
+/// Quote an identifier for DuckDB, doubling any embedded `"`.
+pub(crate) fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn is_integer_type(dtype: &str) -> bool {
+    let t = dtype.to_uppercase();
+    t.contains("INT") && !t.starts_with("INTERVAL")
+}
+
+/// Build the `SELECT ... GROUP BY` statement for `table`, checking every column
+/// against `columns` (name, DuckDB type) and every alias for emptiness and clashes.
+pub(crate) fn build_group_aggregate_sql(
+    table: &str,
+    group_columns: &[&str],
+    aggs: &[AggSpec],
+    columns: &[(String, String)],
+) -> Result<GroupAggregateQuery> {
+    if group_columns.is_empty() {
+        return Err(RustoraError::Session(
+            "Group aggregate requires at least one group column".to_string(),
+        ));
+    }
+    if aggs.is_empty() {
+        return Err(RustoraError::Session(
+            "Group aggregate requires at least one aggregate".to_string(),
+        ));
+    }
+
+    let column_type = |name: &str| {
+        columns
+            .iter()
+            .find(|(c, _)| c == name)
+            .map(|(_, t)| t.as_str())
+            .ok_or_else(|| RustoraError::ColumnNotFound(name.to_string()))
+    };
+
+    let mut keys = Vec::with_capacity(group_columns.len());
+    for c in group_columns {
+        column_type(c)?;
+        keys.push(quote_ident(c));
+    }
+
+    let mut select = keys.clone();
+    let mut aliases: Vec<&str> = group_columns.to_vec();
+    let mut integer_sums = Vec::with_capacity(aggs.len());
+    for agg in aggs {
+        if agg.alias.trim().is_empty() {
+            return Err(RustoraError::Session("Aggregate alias is empty".to_string()));
+        }
+        if aliases.contains(&agg.alias.as_str()) {
+            return Err(RustoraError::Session(format!(
+                "Duplicate output column: {}",
+                agg.alias
+            )));
+        }
+        aliases.push(&agg.alias);
+
+        let mut is_integer_sum = false;
+        let expr = match (agg.function, agg.column.as_deref()) {
+            (AggFunction::Count, None) => "COUNT(*)".to_string(),
+            (_, None) => {
+                return Err(RustoraError::Session(format!(
+                    "Aggregate '{}' requires a column",
+                    agg.alias
+                )))
+            }
+            (function, Some(c)) => {
+                let dtype = column_type(c)?;
+                let col = quote_ident(c);
+                match function {
+                    AggFunction::Count => format!("COUNT({})", col),
+                    AggFunction::CountDistinct => format!("COUNT(DISTINCT {})", col),
+                    // DuckDB widens integer sums to HUGEINT, which may not fit in a
+                    // BIGINT; ship the exact digits and decode each cell in Rust.
+                    AggFunction::Sum if is_integer_type(dtype) => {
+                        is_integer_sum = true;
+                        format!("CAST(SUM({}) AS VARCHAR)", col)
+                    }
+                    AggFunction::Sum => format!("CAST(SUM({}) AS DOUBLE)", col),
+                    AggFunction::Avg => format!("AVG({})", col),
+                    AggFunction::Min => format!("MIN({})", col),
+                    AggFunction::Max => format!("MAX({})", col),
+                }
+            }
+        };
+        integer_sums.push(is_integer_sum);
+        select.push(format!("{} AS {}", expr, quote_ident(&agg.alias)));
+    }
+
+    let keys = keys.join(", ");
+    let sql = format!(
+        "SELECT {} FROM {} GROUP BY {} ORDER BY {}",
+        select.join(", "),
+        quote_ident(table),
+        keys,
+        keys
+    );
+    Ok(GroupAggregateQuery { sql, integer_sums })
+}
+
+/// Decode an integer sum shipped as text: `Int` when it fits in an `i64`, else `Float`.
+fn integer_sum_value(value: AnyValue<'_>) -> GroupValue {
+    let text = match &value {
+        AnyValue::String(s) => *s,
+        AnyValue::StringOwned(s) => s.as_str(),
+        _ => return GroupValue::from(value),
+    };
+    match text.parse::<i64>() {
+        Ok(v) => GroupValue::Int(v),
+        Err(_) => text
+            .parse::<f64>()
+            .map_or_else(|_| GroupValue::Text(text.to_string()), GroupValue::Float),
+    }
+}
+
+/// Split a result frame into typed rows: the first `key_count` columns are keys,
+/// and the value columns flagged in `integer_sums` are decoded as integer sums.
+pub(crate) fn dataframe_to_group_rows(
+    df: &DataFrame,
+    key_count: usize,
+    integer_sums: &[bool],
+) -> Result<Vec<GroupRow>> {
+    let columns = df.get_columns();
+    let mut rows = Vec::with_capacity(df.height());
+    for i in 0..df.height() {
+        let mut keys = Vec::with_capacity(key_count);
+        let mut values = Vec::with_capacity(columns.len().saturating_sub(key_count));
+        for (j, column) in columns.iter().enumerate() {
+            let value = column.get(i)?;
+            if j < key_count {
+                keys.push(GroupValue::from(value));
+            } else if integer_sums.get(j - key_count).copied().unwrap_or(false) {
+                values.push(integer_sum_value(value));
+            } else {
+                values.push(GroupValue::from(value));
+            }
+        }
+        rows.push(GroupRow { keys, values });
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns() -> Vec<(String, String)> {
+        vec![
+            ("city".to_string(), "VARCHAR".to_string()),
+            ("age".to_string(), "BIGINT".to_string()),
+            ("score".to_string(), "DOUBLE".to_string()),
+        ]
+    }
+
+    fn agg(function: AggFunction, column: Option<&str>, alias: &str) -> AggSpec {
+        AggSpec {
+            function,
+            column: column.map(|c| c.to_string()),
+            alias: alias.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_sql_quotes_and_casts() {
+        let query = build_group_aggregate_sql(
+            "people",
+            &["city"],
+            &[
+                agg(AggFunction::Count, None, "n"),
+                agg(AggFunction::Sum, Some("age"), "total_age"),
+                agg(AggFunction::Sum, Some("score"), "total_score"),
+            ],
+            &columns(),
+        )
+        .unwrap();
+        assert_eq!(
+            query.sql,
+            "SELECT \"city\", COUNT(*) AS \"n\", CAST(SUM(\"age\") AS VARCHAR) AS \"total_age\", \
+             CAST(SUM(\"score\") AS DOUBLE) AS \"total_score\" FROM \"people\" \
+             GROUP BY \"city\" ORDER BY \"city\""
+        );
+        assert_eq!(query.integer_sums, vec![false, true, false]);
+    }
+
+    #[test]
+    fn test_build_sql_rejects_bad_input() {
+        let unknown = build_group_aggregate_sql(
+            "people",
+            &["town"],
+            &[agg(AggFunction::Count, None, "n")],
+            &columns(),
+        );
+        assert!(matches!(unknown, Err(RustoraError::ColumnNotFound(c)) if c == "town"));
+
+        let missing_column = build_group_aggregate_sql(
+            "people",
+            &["city"],
+            &[agg(AggFunction::Avg, None, "avg")],
+            &columns(),
+        );
+        assert!(missing_column.is_err());
+
+        let duplicate = build_group_aggregate_sql(
+            "people",
+            &["city"],
+            &[agg(AggFunction::Count, None, "city")],
+            &columns(),
+        );
+        assert!(duplicate.is_err());
+
+        assert!(AggFunction::parse("median; DROP TABLE people").is_err());
+        assert_eq!(AggFunction::parse("AVG").unwrap(), AggFunction::Avg);
+    }
+}
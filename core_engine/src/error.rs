@@ -34,6 +34,9 @@ pub enum RustoraError {
 
     #[error("Session error: {0}")]
     Session(String),
+
+    #[error("Read-only mode: {0}")]
+    ReadOnly(String),
 }
 
 pub type Result<T> = std::result::Result<T, RustoraError>;
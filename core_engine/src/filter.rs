@@ -10,10 +10,18 @@ pub struct FilterCondition {
     pub value: String,
 }
 
+/// Comparison operators for a [`FilterCondition`].
+///
+/// `Equals`/`NotEquals` follow standard SQL: comparing with a NULL yields NULL, so
+/// rows where the column is NULL never match either of them. `NullSafeEquals` and
+/// `NullSafeNotEquals` use `IS [NOT] DISTINCT FROM`, which treats NULL as an ordinary
+/// value: `NullSafeNotEquals` keeps NULL rows.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum FilterOperator {
     Equals,
     NotEquals,
+    NullSafeEquals,
+    NullSafeNotEquals,
     GreaterThan,
     GreaterThanOrEqual,
     LessThan,
@@ -54,7 +62,7 @@ impl FilterSpec {
         let clauses: Vec<String> = self
             .conditions
             .iter()
-            .map(|c| condition_to_sql(c))
+            .map(condition_to_sql)
             .collect::<Result<Vec<_>>>()?;
 
         let joiner = match self.logic {
@@ -66,6 +74,258 @@ impl FilterSpec {
     }
 }
 
+/// Why a filter condition failed validation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FilterIssueKind {
+    /// The column does not exist in the dataset.
+    UnknownColumn,
+    /// The column name contains characters the SQL builder refuses to quote.
+    InvalidColumnName,
+    /// The value cannot be coerced to the column's type, or the operator does not apply to it.
+    TypeMismatch,
+    /// The operator needs a value but none was given.
+    MissingValue,
+}
+
+/// A problem with one condition of a [`FilterSpec`], found without executing it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterValidationIssue {
+    /// Index of the offending condition in [`FilterSpec::conditions`].
+    pub condition_index: usize,
+    pub column: String,
+    pub kind: FilterIssueKind,
+    pub message: String,
+}
+
+impl FilterSpec {
+    /// Check every condition against a dataset schema given as `(column_name, column_type)`
+    /// pairs. Types may be DuckDB names (`BIGINT`, `VARCHAR`) or Polars names (`i64`, `str`).
+    /// Returns one issue per failing condition; an empty vector means the spec is runnable.
+    pub fn validate(&self, schema: &[(String, String)]) -> Vec<FilterValidationIssue> {
+        self.conditions
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| validate_condition(i, c, schema))
+            .collect()
+    }
+}
+
+/// Coarse classification of a column type, used only for value coercion checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Numeric,
+    Text,
+    Boolean,
+    Date,
+    Timestamp,
+    /// Lists, arrays, structs, maps and unions.
+    Nested,
+    Other,
+}
+
+fn classify_column_type(dtype: &str) -> ColumnKind {
+    let t = dtype.to_lowercase();
+    if t.ends_with("[]")
+        || ["list[", "array[", "struct", "map(", "union("]
+            .iter()
+            .any(|prefix| t.starts_with(prefix))
+    {
+        ColumnKind::Nested
+    } else if t.starts_with("timestamp") || t.starts_with("datetime") {
+        ColumnKind::Timestamp
+    } else if t == "date" {
+        ColumnKind::Date
+    } else if t == "boolean" || t == "bool" {
+        ColumnKind::Boolean
+    } else if t == "varchar" || t == "text" || t == "str" || t == "string" || t == "cat" {
+        ColumnKind::Text
+    } else if is_numeric_type_name(&t) {
+        ColumnKind::Numeric
+    } else {
+        ColumnKind::Other
+    }
+}
+
+/// Scalar numeric type names, matched exactly so that nested types such as
+/// `INTEGER[]` or `STRUCT(a INTEGER)` and `INTERVAL` are not mistaken for numbers.
+fn is_numeric_type_name(t: &str) -> bool {
+    let parameterized = |prefix: &str| {
+        t.strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix(')'))
+            .is_some_and(|args| !args.contains([')', '(']))
+    };
+    parameterized("decimal(")
+        || parameterized("numeric(")
+        || matches!(
+            t,
+            "tinyint"
+                | "smallint"
+                | "integer"
+                | "int"
+                | "bigint"
+                | "hugeint"
+                | "utinyint"
+                | "usmallint"
+                | "uinteger"
+                | "ubigint"
+                | "uhugeint"
+                | "int1"
+                | "int2"
+                | "int4"
+                | "int8"
+                | "decimal"
+                | "numeric"
+                | "double"
+                | "float"
+                | "float4"
+                | "float8"
+                | "real"
+                | "i8"
+                | "i16"
+                | "i32"
+                | "i64"
+                | "i128"
+                | "u8"
+                | "u16"
+                | "u32"
+                | "u64"
+                | "f32"
+                | "f64"
+        )
+}
+
+/// Accepts `YYYY-MM-DD`.
+fn is_date_literal(s: &str) -> bool {
+    let parts: Vec<&str> = s.split('-').collect();
+    parts.len() == 3
+        && parts[0].len() == 4
+        && parts[1].len() == 2
+        && parts[2].len() == 2
+        && parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Accepts a date literal optionally followed by a time part (`YYYY-MM-DD HH:MM:SS`).
+fn is_timestamp_literal(s: &str) -> bool {
+    match s.split_once([' ', 'T']) {
+        Some((date, time)) => is_date_literal(date) && !time.is_empty(),
+        None => is_date_literal(s),
+    }
+}
+
+fn is_boolean_literal(s: &str) -> bool {
+    matches!(
+        s.to_lowercase().as_str(),
+        "true" | "false" | "t" | "f" | "1" | "0"
+    )
+}
+
+fn validate_condition(
+    index: usize,
+    cond: &FilterCondition,
+    schema: &[(String, String)],
+) -> Option<FilterValidationIssue> {
+    let issue = |kind: FilterIssueKind, message: String| FilterValidationIssue {
+        condition_index: index,
+        column: cond.column.clone(),
+        kind,
+        message,
+    };
+
+    if sanitize_column_name(&cond.column).is_err() {
+        return Some(issue(
+            FilterIssueKind::InvalidColumnName,
+            format!("Invalid column name: {}", cond.column),
+        ));
+    }
+
+    let dtype = match schema.iter().find(|(name, _)| name == &cond.column) {
+        Some((_, dtype)) => dtype,
+        None => {
+            return Some(issue(
+                FilterIssueKind::UnknownColumn,
+                format!("Column not found: {}", cond.column),
+            ))
+        }
+    };
+
+    let is_text_op = matches!(
+        cond.operator,
+        FilterOperator::Contains
+            | FilterOperator::NotContains
+            | FilterOperator::StartsWith
+            | FilterOperator::EndsWith
+    );
+    let is_ordering_op = matches!(
+        cond.operator,
+        FilterOperator::GreaterThan
+            | FilterOperator::GreaterThanOrEqual
+            | FilterOperator::LessThan
+            | FilterOperator::LessThanOrEqual
+    );
+
+    if matches!(cond.operator, FilterOperator::IsNull | FilterOperator::IsNotNull) {
+        return None;
+    }
+    if is_ordering_op && cond.value.is_empty() {
+        return Some(issue(
+            FilterIssueKind::MissingValue,
+            format!("{:?} on '{}' requires a value", cond.operator, cond.column),
+        ));
+    }
+
+    let kind = classify_column_type(dtype);
+    if cond.value.is_empty() && !is_text_op && !matches!(kind, ColumnKind::Text | ColumnKind::Other)
+    {
+        return Some(issue(
+            FilterIssueKind::MissingValue,
+            format!("{:?} on '{}' requires a value", cond.operator, cond.column),
+        ));
+    }
+    if is_text_op {
+        return match kind {
+            ColumnKind::Text | ColumnKind::Other => None,
+            _ => Some(issue(
+                FilterIssueKind::TypeMismatch,
+                format!(
+                    "{:?} only applies to text columns; '{}' is {}",
+                    cond.operator, cond.column, dtype
+                ),
+            )),
+        };
+    }
+
+    // The SQL builder emits numeric-looking values unquoted, which DuckDB would try
+    // to cast every value of a text or nested column to.
+    if matches!(kind, ColumnKind::Text | ColumnKind::Nested) && is_numeric(&cond.value) {
+        return Some(issue(
+            FilterIssueKind::TypeMismatch,
+            format!(
+                "Value '{}' is numeric and would be compared as a number against column '{}' of type {}",
+                cond.value, cond.column, dtype
+            ),
+        ));
+    }
+
+    let coercible = match kind {
+        ColumnKind::Numeric => is_numeric(&cond.value),
+        ColumnKind::Boolean => is_boolean_literal(&cond.value),
+        ColumnKind::Date => is_date_literal(&cond.value),
+        ColumnKind::Timestamp => is_timestamp_literal(&cond.value),
+        ColumnKind::Text | ColumnKind::Nested | ColumnKind::Other => true,
+    };
+    if coercible {
+        None
+    } else {
+        Some(issue(
+            FilterIssueKind::TypeMismatch,
+            format!(
+                "Value '{}' cannot be compared with column '{}' of type {}",
+                cond.value, cond.column, dtype
+            ),
+        ))
+    }
+}
+
 fn sanitize_column_name(name: &str) -> Result<String> {
     if name.is_empty() || name.len() > 256 {
         return Err(RustoraError::ColumnNotFound(name.to_string()));
@@ -105,6 +365,12 @@ fn condition_to_sql(cond: &FilterCondition) -> Result<String> {
                 format!("{} != '{}'", col, escaped_val)
             }
         }
+        FilterOperator::NullSafeEquals => {
+            format!("{} IS NOT DISTINCT FROM {}", col, format_comparison_value(&cond.value))
+        }
+        FilterOperator::NullSafeNotEquals => {
+            format!("{} IS DISTINCT FROM {}", col, format_comparison_value(&cond.value))
+        }
         FilterOperator::GreaterThan => format!("{} > {}", col, format_comparison_value(&cond.value)),
         FilterOperator::GreaterThanOrEqual => {
             format!("{} >= {}", col, format_comparison_value(&cond.value))
@@ -321,6 +587,103 @@ mod tests {
         assert!(spec.to_sql_where().is_err());
     }
 
+    #[test]
+    fn test_null_safe_operators() {
+        let spec = FilterSpec {
+            conditions: vec![
+                FilterCondition {
+                    column: "city".to_string(),
+                    operator: FilterOperator::NullSafeNotEquals,
+                    value: "Boston".to_string(),
+                },
+                FilterCondition {
+                    column: "age".to_string(),
+                    operator: FilterOperator::NullSafeEquals,
+                    value: "30".to_string(),
+                },
+            ],
+            logic: FilterLogic::Or,
+        };
+        let sql = spec.to_sql_where().unwrap();
+        assert_eq!(
+            sql,
+            "\"city\" IS DISTINCT FROM 'Boston' OR \"age\" IS NOT DISTINCT FROM 30"
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_unknown_column_and_missing_value() {
+        let schema = vec![
+            ("age".to_string(), "BIGINT".to_string()),
+            ("city".to_string(), "VARCHAR".to_string()),
+        ];
+        let spec = FilterSpec {
+            conditions: vec![
+                FilterCondition {
+                    column: "agee".to_string(),
+                    operator: FilterOperator::Equals,
+                    value: "30".to_string(),
+                },
+                FilterCondition {
+                    column: "age".to_string(),
+                    operator: FilterOperator::LessThan,
+                    value: String::new(),
+                },
+                FilterCondition {
+                    column: "city".to_string(),
+                    operator: FilterOperator::IsNull,
+                    value: String::new(),
+                },
+            ],
+            logic: FilterLogic::And,
+        };
+        let issues = spec.validate(&schema);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].condition_index, 0);
+        assert_eq!(issues[0].kind, FilterIssueKind::UnknownColumn);
+        assert_eq!(issues[1].condition_index, 1);
+        assert_eq!(issues[1].kind, FilterIssueKind::MissingValue);
+    }
+
+    #[test]
+    fn test_validate_type_classification() {
+        let schema = vec![
+            ("name".to_string(), "VARCHAR".to_string()),
+            ("age".to_string(), "INTEGER".to_string()),
+            ("gap".to_string(), "INTERVAL".to_string()),
+            ("tags".to_string(), "INTEGER[]".to_string()),
+            ("pair".to_string(), "STRUCT(a INTEGER)".to_string()),
+            ("amounts".to_string(), "DECIMAL(10,2)[]".to_string()),
+            ("price".to_string(), "DECIMAL(10,2)".to_string()),
+        ];
+        let cond = |column: &str, operator: FilterOperator, value: &str| FilterCondition {
+            column: column.to_string(),
+            operator,
+            value: value.to_string(),
+        };
+        let spec = FilterSpec {
+            conditions: vec![
+                cond("name", FilterOperator::Equals, "30"),
+                cond("age", FilterOperator::NullSafeEquals, ""),
+                cond("gap", FilterOperator::Equals, "1 day"),
+                cond("tags", FilterOperator::Equals, "[1, 2]"),
+                cond("pair", FilterOperator::Equals, "{'a': 1}"),
+                cond("name", FilterOperator::Contains, "30"),
+                cond("amounts", FilterOperator::Equals, "5"),
+                cond("price", FilterOperator::Equals, "5"),
+            ],
+            logic: FilterLogic::And,
+        };
+        let issues = spec.validate(&schema);
+        assert_eq!(issues.len(), 3);
+        assert_eq!(issues[0].condition_index, 0);
+        assert_eq!(issues[0].kind, FilterIssueKind::TypeMismatch);
+        assert_eq!(issues[1].condition_index, 1);
+        assert_eq!(issues[1].kind, FilterIssueKind::MissingValue);
+        assert_eq!(issues[2].condition_index, 6);
+        assert_eq!(issues[2].kind, FilterIssueKind::TypeMismatch);
+    }
+
     #[test]
     fn test_like_wildcards_escaped() {
         let spec = FilterSpec {
@@ -16,14 +16,21 @@
 //! let ipc_bytes = session.get_preview_ipc(&name, 100).unwrap();
 //! ```
 
+pub mod aggregate;
 pub mod error;
 pub mod filter;
 pub mod session;
 pub mod storage;
 pub mod transform_history;
 
+pub use aggregate::{AggFunction, AggSpec, GroupRow, GroupValue};
 pub use error::{Result, RustoraError};
-pub use filter::{FilterCondition, FilterLogic, FilterOperator, FilterSpec};
-pub use session::RustoraSession;
-pub use storage::{CsvImportOptions, DuckStorage};
+pub use filter::{
+    FilterCondition, FilterIssueKind, FilterLogic, FilterOperator, FilterSpec,
+    FilterValidationIssue,
+};
+pub use session::{DatasetEvent, DatasetKind, RustoraSession};
+pub use storage::{
+    CsvExportOptions, CsvImportOptions, CsvRejectedRow, DuckStorage, RaggedRowMode,
+};
 pub use transform_history::{StepEntry, TransformHistory, TransformStep};
@@ -1,15 +1,30 @@
+use crate::aggregate::{
+    build_group_aggregate_sql, dataframe_to_group_rows, quote_ident, AggSpec, GroupRow,
+};
 use crate::error::{Result, RustoraError};
-use crate::filter::FilterSpec;
-use crate::storage::{CsvImportOptions, DuckStorage};
+use crate::filter::{FilterSpec, FilterValidationIssue};
+use crate::storage::{
+    ensure_parent_dir, sanitize_table_name, CsvExportOptions, CsvImportOptions, CsvRejectedRow,
+    DuckStorage,
+};
 use crate::transform_history::{StepEntry, TransformHistory, TransformStep};
 use polars::prelude::*;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Cursor;
 use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use tracing::info;
 
+/// Rows fetched per round-trip when streaming a DuckDB table to an IPC file.
+const IPC_EXPORT_CHUNK_ROWS: u64 = 100_000;
+
+/// Most offsets a single [`RustoraSession::prefetch_chunks`] call accepts, so one
+/// scroll-ahead request cannot hold the session for an unbounded number of pages.
+pub const MAX_PREFETCH_CHUNKS: usize = 16;
+
 /// Metadata about a loaded dataset.
 #[derive(Debug, Clone)]
 pub struct DatasetInfo {
@@ -25,6 +40,32 @@ pub struct DatasetInfo {
     pub estimated_size_bytes: Option<u64>,
 }
 
+/// Where a dataset lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatasetKind {
+    /// A table in the DuckDB project database.
+    Persistent,
+    /// An in-memory Polars LazyFrame.
+    Transient,
+}
+
+/// A change to the set of datasets, delivered to observers registered with
+/// [`RustoraSession::on_change`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DatasetEvent {
+    /// A new dataset name appeared (import, scan, SQL result, or transform).
+    Created { name: String },
+    /// A dataset was removed.
+    Dropped { name: String },
+    /// A dataset was renamed in place.
+    Renamed { from: String, to: String },
+    /// An existing dataset name was overwritten with new contents.
+    Modified { name: String },
+}
+
+type ChangeObserver = Box<dyn Fn(&DatasetEvent) + Send>;
+
 /// The core session that manages all data operations.
 ///
 /// Architecture:
@@ -40,6 +81,10 @@ pub struct RustoraSession {
     counter: Arc<AtomicU64>,
     /// Transform history per dataset (keyed by result table name).
     histories: HashMap<String, TransformHistory>,
+    /// Callbacks notified whenever a dataset is created, dropped, renamed, or modified.
+    observers: Vec<ChangeObserver>,
+    /// When set, only read paths are allowed (see [`RustoraSession::set_readonly_queries`]).
+    readonly_queries: bool,
 }
 
 impl RustoraSession {
@@ -54,32 +99,58 @@ impl RustoraSession {
             transient: HashMap::new(),
             counter: Arc::new(AtomicU64::new(0)),
             histories: HashMap::new(),
+            observers: Vec::new(),
+            readonly_queries: false,
         }
     }
 
     /// Open a persistent project file (.duckdb).
     /// Existing tables in the database become immediately available.
+    /// In read-only query mode the project file must already exist, and the
+    /// step-history table is not created.
     pub fn open_project(&mut self, db_path: &str) -> Result<Vec<String>> {
         info!(db_path, "opening project");
-        let storage = DuckStorage::open(db_path)?;
-        let _ = storage.ensure_steps_table();
+        if self.readonly_queries && !Path::new(db_path).exists() {
+            return Err(RustoraError::ReadOnly(format!(
+                "cannot create project '{}' while the session only permits read-only queries",
+                db_path
+            )));
+        }
+        let mut storage = DuckStorage::open(db_path)?;
+        storage.set_readonly_queries(self.readonly_queries);
+        if !self.readonly_queries {
+            let _ = storage.ensure_steps_table();
+        }
         let tables = storage.list_tables()?;
         info!(db_path, table_count = tables.len(), "project opened");
-        self.storage = Some(storage);
-        self.transient.clear();
-        self.histories.clear();
+        self.replace_storage(storage);
         self.load_histories_from_storage();
         Ok(tables)
     }
 
-    /// Create a new project file (.duckdb).
+    /// Create a new project file (.duckdb). Not allowed in read-only query mode.
     pub fn new_project(&mut self, db_path: &str) -> Result<()> {
-        let storage = DuckStorage::open(db_path)?;
+        self.ensure_writable("new_project")?;
+        let mut storage = DuckStorage::open(db_path)?;
+        storage.set_readonly_queries(self.readonly_queries);
         let _ = storage.ensure_steps_table();
+        self.replace_storage(storage);
+        Ok(())
+    }
+
+    /// Swap in a project's storage, dropping every dataset of the previous one.
+    /// Observers see `Dropped` for each old dataset, then `Created` for each table
+    /// of the new project.
+    fn replace_storage(&mut self, storage: DuckStorage) {
+        for name in self.list_datasets() {
+            self.notify(DatasetEvent::Dropped { name });
+        }
         self.storage = Some(storage);
         self.transient.clear();
         self.histories.clear();
-        Ok(())
+        for name in self.list_datasets() {
+            self.notify(DatasetEvent::Created { name });
+        }
     }
 
     /// Get the current project path.
@@ -96,6 +167,70 @@ impl RustoraSession {
         self.counter.fetch_add(1, Ordering::Relaxed) + 1
     }
 
+    // -----------------------------------------------------------------------
+    // Read-only Query Mode
+    // -----------------------------------------------------------------------
+
+    /// Restrict this session to read paths: previews, chunks, metadata, and
+    /// single SELECT statements through `execute_sql_to_ipc`. Imports, exports,
+    /// transforms, `execute_sql`, and dataset removal fail with `ReadOnly`.
+    /// This is a session policy; the project file itself stays writable.
+    /// The storage layer is told as well, so every query path it serves only
+    /// accepts a single statement while the mode is on.
+    pub fn set_readonly_queries(&mut self, enabled: bool) {
+        info!(enabled, "read-only query mode changed");
+        self.readonly_queries = enabled;
+        if let Some(storage) = self.storage.as_mut() {
+            storage.set_readonly_queries(enabled);
+            // A project opened in read-only mode has no step-history table yet.
+            if !enabled {
+                let _ = storage.ensure_steps_table();
+            }
+        }
+    }
+
+    /// Whether read-only query mode is enabled.
+    pub fn readonly_queries(&self) -> bool {
+        self.readonly_queries
+    }
+
+    fn ensure_writable(&self, operation: &str) -> Result<()> {
+        if self.readonly_queries {
+            return Err(RustoraError::ReadOnly(format!(
+                "{} is not allowed while the session only permits read-only queries",
+                operation
+            )));
+        }
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Change Notification
+    // -----------------------------------------------------------------------
+
+    /// Register an observer called synchronously after every dataset change.
+    /// Observers run on the thread performing the operation and must not call
+    /// back into the session.
+    pub fn on_change(&mut self, observer: impl Fn(&DatasetEvent) + Send + 'static) {
+        self.observers.push(Box::new(observer));
+    }
+
+    fn notify(&self, event: DatasetEvent) {
+        for observer in &self.observers {
+            observer(&event);
+        }
+    }
+
+    /// Emit `Created` or `Modified` depending on whether `name` existed before the write.
+    fn notify_written(&self, name: &str, existed: bool) {
+        let name = name.to_string();
+        self.notify(if existed {
+            DatasetEvent::Modified { name }
+        } else {
+            DatasetEvent::Created { name }
+        });
+    }
+
     fn generate_name(&self, file_path: &str) -> String {
         let stem = Path::new(file_path)
             .file_stem()
@@ -117,6 +252,10 @@ impl RustoraSession {
             }
         }
         self.histories.insert(result_table.to_string(), history);
+        // Every transform writes to a freshly generated name.
+        self.notify(DatasetEvent::Created {
+            name: result_table.to_string(),
+        });
     }
 
     fn record_source_step(&mut self, table_name: &str, file_path: &str) {
@@ -162,22 +301,48 @@ impl RustoraSession {
     /// Import a file into the DuckDB database as a persistent table.
     /// This is the primary way to load data. The file is copied into DuckDB storage.
     pub fn import_file(&mut self, file_path: &str, table_name: Option<&str>) -> Result<String> {
+        self.ensure_writable("import")?;
         let storage = self.storage.as_ref().ok_or(RustoraError::NoProjectOpen)?;
 
         let name = match table_name {
             Some(n) => n.to_string(),
             None => self.generate_name(file_path),
         };
+        // Sanitize up front so `existed` looks at the table that will be written.
+        let name = sanitize_table_name(&name);
 
+        let existed = self.find_dataset_kind(&name)?.is_some();
         info!(file_path, table = %name, "importing file into session");
-        storage.import_file(file_path, &name)?;
+        let name = storage.import_file(file_path, &name)?;
         self.record_source_step(&name, file_path);
+        self.notify_written(&name, existed);
+        Ok(name)
+    }
+
+    /// Import a Hive-partitioned Parquet directory tree as a persistent table.
+    /// Partition values encoded in the paths (`year=2024/month=01`) become columns.
+    pub fn import_hive_partitioned(&mut self, root: &str, table_name: Option<&str>) -> Result<String> {
+        self.ensure_writable("import")?;
+        let storage = self.storage.as_ref().ok_or(RustoraError::NoProjectOpen)?;
+
+        let name = match table_name {
+            Some(n) => n.to_string(),
+            None => self.generate_name(root),
+        };
+        // Sanitize up front so `existed` looks at the table that will be written.
+        let name = sanitize_table_name(&name);
+
+        let existed = self.find_dataset_kind(&name)?.is_some();
+        let name = storage.import_hive_partitioned(root, &name)?;
+        self.record_source_step(&name, root);
+        self.notify_written(&name, existed);
         Ok(name)
     }
 
     /// Lazily scan a file via Polars (non-persistent, kept in memory).
     /// For backwards compatibility; prefer `import_file` for persistent storage.
     pub fn scan_file(&mut self, file_path: &str) -> Result<String> {
+        self.ensure_writable("scan_file")?;
         let path = Path::new(file_path);
         if !path.exists() {
             return Err(RustoraError::FileNotFound(file_path.to_string()));
@@ -207,6 +372,7 @@ impl RustoraSession {
         let name = self.generate_name(file_path);
         self.transient.insert(name.clone(), lf);
         self.record_source_step(&name, file_path);
+        self.notify(DatasetEvent::Created { name: name.clone() });
         Ok(name)
     }
 
@@ -229,6 +395,31 @@ impl RustoraSession {
         names
     }
 
+    /// Whether a dataset with this name exists (persistent or transient).
+    pub fn dataset_exists(&self, name: &str) -> bool {
+        self.dataset_kind(name).is_some()
+    }
+
+    /// Report whether `name` is a DuckDB table, a transient LazyFrame, or unknown.
+    /// DuckDB tables take precedence, matching the lookup order of the other methods.
+    /// A failed catalog lookup is reported as unknown; the session itself uses the
+    /// fallible lookup so such errors are not mistaken for a missing dataset.
+    pub fn dataset_kind(&self, name: &str) -> Option<DatasetKind> {
+        self.find_dataset_kind(name).ok().flatten()
+    }
+
+    fn find_dataset_kind(&self, name: &str) -> Result<Option<DatasetKind>> {
+        if let Some(storage) = &self.storage {
+            if storage.table_exists(name)? {
+                return Ok(Some(DatasetKind::Persistent));
+            }
+        }
+        if self.transient.contains_key(name) {
+            return Ok(Some(DatasetKind::Transient));
+        }
+        Ok(None)
+    }
+
     /// List only persistent DuckDB tables.
     pub fn list_tables(&self) -> Result<Vec<String>> {
         self.storage()?.list_tables()
@@ -283,7 +474,7 @@ impl RustoraSession {
     /// Checks DuckDB tables first, then transient LazyFrames.
     pub fn get_preview_ipc(&self, name: &str, limit: u32) -> Result<Vec<u8>> {
         if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
+            if storage.table_exists(name)? {
                 return storage.get_table_preview_ipc(name, limit as u64);
             }
         }
@@ -299,7 +490,7 @@ impl RustoraSession {
     /// Get a paginated chunk of rows as Arrow IPC bytes.
     pub fn get_chunk_ipc(&self, name: &str, offset: u32, limit: u32) -> Result<Vec<u8>> {
         if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
+            if storage.table_exists(name)? {
                 return storage.get_table_chunk_ipc(name, offset as u64, limit as u64);
             }
         }
@@ -312,6 +503,48 @@ impl RustoraSession {
         Err(RustoraError::TableNotFound(name.to_string()))
     }
 
+    /// Fetch several chunks of `limit` rows in one call, keyed by offset.
+    /// The dataset is resolved once, so a scrolling UI can load a window of
+    /// upcoming pages under a single lock instead of one round trip per page.
+    /// Duplicate offsets are fetched once. At most [`MAX_PREFETCH_CHUNKS`] offsets
+    /// are accepted per call.
+    pub fn prefetch_chunks(
+        &self,
+        name: &str,
+        offsets: &[u32],
+        limit: u32,
+    ) -> Result<BTreeMap<u32, Vec<u8>>> {
+        if offsets.len() > MAX_PREFETCH_CHUNKS {
+            return Err(RustoraError::Session(format!(
+                "Cannot prefetch {} chunks in one call; the limit is {}",
+                offsets.len(),
+                MAX_PREFETCH_CHUNKS
+            )));
+        }
+        let mut chunks = BTreeMap::new();
+        match self.find_dataset_kind(name)? {
+            Some(DatasetKind::Persistent) => {
+                let storage = self.storage()?;
+                for &offset in offsets {
+                    if let Entry::Vacant(slot) = chunks.entry(offset) {
+                        slot.insert(storage.get_table_chunk_ipc(name, offset as u64, limit as u64)?);
+                    }
+                }
+            }
+            Some(DatasetKind::Transient) => {
+                let lf = &self.transient[name];
+                for &offset in offsets {
+                    if let Entry::Vacant(slot) = chunks.entry(offset) {
+                        let df = lf.clone().slice(offset as i64, limit).collect()?;
+                        slot.insert(Self::dataframe_to_ipc_bytes(df)?);
+                    }
+                }
+            }
+            None => return Err(RustoraError::TableNotFound(name.to_string())),
+        }
+        Ok(chunks)
+    }
+
     /// Get the total row count for a dataset.
     pub fn get_row_count(&self, name: &str) -> Result<usize> {
         if let Some(storage) = &self.storage {
@@ -339,6 +572,7 @@ impl RustoraSession {
     /// Execute a SQL query via DuckDB. Result is stored as a new table.
     /// Returns the result table name.
     pub fn execute_sql(&mut self, sql: &str) -> Result<String> {
+        self.ensure_writable("execute_sql")?;
         let storage = self.storage.as_ref().ok_or(RustoraError::NoProjectOpen)?;
 
         let result_name = format!("sql_result_{}", self.next_counter());
@@ -350,16 +584,30 @@ impl RustoraSession {
             result_name.clone(),
         );
         self.histories.insert(result_name.clone(), history);
+        self.notify(DatasetEvent::Created {
+            name: result_name.clone(),
+        });
         Ok(result_name)
     }
 
     /// Execute a SQL query and return the result directly as Arrow IPC bytes
-    /// (without persisting as a table). For read-only queries.
+    /// (without persisting as a table). For read-only queries; in read-only
+    /// query mode anything other than a single SELECT statement is rejected.
     pub fn execute_sql_to_ipc(&self, sql: &str) -> Result<Vec<u8>> {
         let storage = self.storage.as_ref().ok_or(RustoraError::NoProjectOpen)?;
+        if self.readonly_queries {
+            return storage.query_select_only_to_ipc(sql);
+        }
         storage.query_to_ipc(sql)
     }
 
+    /// List the tables a SQL query reads from (CTE names and subqueries are
+    /// resolved to their underlying tables). The query is planned, not executed;
+    /// text with more than one statement is rejected.
+    pub fn referenced_tables(&self, sql: &str) -> Result<Vec<String>> {
+        self.storage()?.referenced_tables(sql)
+    }
+
     // -----------------------------------------------------------------------
     // Transformations (via DuckDB SQL for persistent, Polars for transient)
     // -----------------------------------------------------------------------
@@ -371,8 +619,9 @@ impl RustoraSession {
         columns: &[&str],
         descending: &[bool],
     ) -> Result<String> {
+        self.ensure_writable("sort")?;
         if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
+            if storage.table_exists(name)? {
                 let order_clauses: Vec<String> = columns
                     .iter()
                     .zip(descending.iter())
@@ -414,6 +663,7 @@ impl RustoraSession {
 
     /// Filter a dataset with a Polars expression (transient datasets only).
     pub fn filter_dataset(&mut self, name: &str, predicate: Expr) -> Result<String> {
+        self.ensure_writable("filter")?;
         let lf = self
             .transient
             .get(name)
@@ -421,7 +671,9 @@ impl RustoraSession {
 
         let filtered = lf.clone().filter(predicate);
         let new_name = format!("{}_filtered", name);
+        let existed = self.find_dataset_kind(&new_name)?.is_some();
         self.transient.insert(new_name.clone(), filtered);
+        self.notify_written(&new_name, existed);
         Ok(new_name)
     }
 
@@ -432,9 +684,10 @@ impl RustoraSession {
         name: &str,
         where_clause: &str,
     ) -> Result<String> {
+        self.ensure_writable("filter")?;
         // For DuckDB tables, use SQL
         if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
+            if storage.table_exists(name)? {
                 let sql = format!(
                     "SELECT * FROM \"{}\" WHERE {}",
                     name, where_clause
@@ -465,6 +718,23 @@ impl RustoraSession {
         self.filter_dataset_sql(name, &where_clause)
     }
 
+    /// Validate a structured FilterSpec against a dataset's schema without running it.
+    /// Returns per-condition issues (unknown column, type mismatch, missing value);
+    /// an empty vector means the filter can be applied.
+    pub fn validate_filter(
+        &self,
+        name: &str,
+        spec: &FilterSpec,
+    ) -> Result<Vec<FilterValidationIssue>> {
+        let info = self.dataset_info(name)?;
+        let schema: Vec<(String, String)> = info
+            .column_names
+            .into_iter()
+            .zip(info.column_dtypes)
+            .collect();
+        Ok(spec.validate(&schema))
+    }
+
     /// Group a dataset by columns with aggregations.
     /// `agg_exprs` are SQL aggregate expressions like ["AVG(salary)", "COUNT(*)", "SUM(amount)"].
     pub fn group_by(
@@ -473,8 +743,9 @@ impl RustoraSession {
         group_columns: &[&str],
         agg_exprs: &[&str],
     ) -> Result<String> {
+        self.ensure_writable("group_by")?;
         if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
+            if storage.table_exists(name)? {
                 let group_cols = group_columns
                     .iter()
                     .map(|c| format!("\"{}\"", c))
@@ -501,6 +772,44 @@ impl RustoraSession {
         Err(RustoraError::TableNotFound(name.to_string()))
     }
 
+    /// Group a persistent table and compute typed aggregates, returning the rows
+    /// directly instead of materializing a table. Group and aggregate columns are
+    /// checked against the table schema, and functions are limited to [`AggFunction`].
+    /// Rows are ordered by the group columns.
+    ///
+    /// Only persistent tables are supported: a transient dataset yields a
+    /// `Session` error rather than `TableNotFound`.
+    ///
+    /// [`AggFunction`]: crate::aggregate::AggFunction
+    pub fn group_aggregate(
+        &self,
+        name: &str,
+        group_columns: &[&str],
+        aggs: &[AggSpec],
+    ) -> Result<Vec<GroupRow>> {
+        if self.transient.contains_key(name) {
+            return Err(RustoraError::Session(format!(
+                "group_aggregate only supports persistent tables; '{}' is a transient dataset",
+                name
+            )));
+        }
+        let storage = self.storage()?;
+        if !storage.table_exists(name)? {
+            return Err(RustoraError::TableNotFound(name.to_string()));
+        }
+
+        let info = storage.table_info(name)?;
+        let schema: Vec<(String, String)> = info
+            .column_names
+            .into_iter()
+            .zip(info.column_types)
+            .collect();
+        let query = build_group_aggregate_sql(name, group_columns, aggs, &schema)?;
+        let bytes = storage.query_to_ipc(&query.sql)?;
+        let df = IpcStreamReader::new(Cursor::new(bytes)).finish()?;
+        dataframe_to_group_rows(&df, group_columns.len(), &query.integer_sums)
+    }
+
     /// Add a calculated column to a dataset via a SQL expression.
     /// Example: expr = "salary * 12", alias = "annual_salary"
     pub fn add_calculated_column(
@@ -509,8 +818,9 @@ impl RustoraSession {
         expr: &str,
         alias: &str,
     ) -> Result<String> {
+        self.ensure_writable("add_calculated_column")?;
         if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
+            if storage.table_exists(name)? {
                 let sql = format!(
                     "SELECT *, ({}) AS \"{}\" FROM \"{}\"",
                     expr, alias, name
@@ -532,7 +842,7 @@ impl RustoraSession {
     /// Returns IPC bytes of a stats table with rows: count, null_count, min, max, mean, std.
     pub fn summary_stats_ipc(&self, name: &str) -> Result<Vec<u8>> {
         if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
+            if storage.table_exists(name)? {
                 // Use DuckDB SUMMARIZE for comprehensive stats
                 let sql = format!("SUMMARIZE SELECT * FROM \"{}\"", name);
                 return storage.query_to_ipc(&sql);
@@ -549,8 +859,9 @@ impl RustoraSession {
     // -----------------------------------------------------------------------
 
     pub fn remove_columns(&mut self, name: &str, columns: &[&str]) -> Result<String> {
+        self.ensure_writable("remove_columns")?;
         if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
+            if storage.table_exists(name)? {
                 let info = storage.table_info(name)?;
                 let keep: Vec<String> = info
                     .column_names
@@ -580,24 +891,54 @@ impl RustoraSession {
     }
 
     pub fn keep_columns(&mut self, name: &str, columns: &[&str]) -> Result<String> {
-        if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
-                let keep: Vec<String> =
-                    columns.iter().map(|c| format!("\"{}\"", c)).collect();
-                let sql = format!("SELECT {} FROM \"{}\"", keep.join(", "), name);
-                let result_name = format!("{}_kept_{}", name, self.next_counter());
-                storage.execute_sql_to_table(&sql, &result_name)?;
-                self.record_step(
-                    name,
-                    &result_name,
-                    TransformStep::KeepColumns {
-                        columns: columns.iter().map(|c| c.to_string()).collect(),
-                    },
-                );
-                return Ok(result_name);
+        self.select_columns(name, columns)
+    }
+
+    /// Project a dataset onto exactly `columns`, in the order given.
+    /// Every column must exist; the first missing one is reported as `ColumnNotFound`.
+    /// Transient datasets are projected with Polars `select`.
+    pub fn select_columns(&mut self, name: &str, columns: &[&str]) -> Result<String> {
+        self.ensure_writable("select_columns")?;
+        if columns.is_empty() {
+            return Err(RustoraError::Session(
+                "Select at least one column".to_string(),
+            ));
+        }
+        let info = self.dataset_info(name)?;
+        for (i, c) in columns.iter().enumerate() {
+            if !info.column_names.iter().any(|existing| existing == c) {
+                return Err(RustoraError::ColumnNotFound(c.to_string()));
+            }
+            if columns[..i].contains(c) {
+                return Err(RustoraError::Session(format!(
+                    "Column '{}' selected more than once",
+                    c
+                )));
             }
         }
-        Err(RustoraError::TableNotFound(name.to_string()))
+        let step = TransformStep::KeepColumns {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+        };
+
+        if info.persistent {
+            let storage = self.storage()?;
+            let keep: Vec<String> = columns.iter().map(|c| format!("\"{}\"", c)).collect();
+            let sql = format!("SELECT {} FROM \"{}\"", keep.join(", "), name);
+            let result_name = format!("{}_kept_{}", name, self.next_counter());
+            storage.execute_sql_to_table(&sql, &result_name)?;
+            self.record_step(name, &result_name, step);
+            return Ok(result_name);
+        }
+
+        let lf = self
+            .transient
+            .get(name)
+            .ok_or(RustoraError::TableNotFound(name.to_string()))?;
+        let selected = lf.clone().select(columns.iter().map(|c| col(*c)).collect::<Vec<_>>());
+        let new_name = format!("{}_kept_{}", name, self.next_counter());
+        self.transient.insert(new_name.clone(), selected);
+        self.record_step(name, &new_name, step);
+        Ok(new_name)
     }
 
     pub fn change_column_type(
@@ -606,8 +947,9 @@ impl RustoraSession {
         column: &str,
         new_type: &str,
     ) -> Result<String> {
+        self.ensure_writable("change_column_type")?;
         if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
+            if storage.table_exists(name)? {
                 let info = storage.table_info(name)?;
                 let select_exprs: Vec<String> = info
                     .column_names
@@ -644,8 +986,9 @@ impl RustoraSession {
         old_col: &str,
         new_col: &str,
     ) -> Result<String> {
+        self.ensure_writable("rename_column")?;
         if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
+            if storage.table_exists(name)? {
                 let info = storage.table_info(name)?;
                 let select_exprs: Vec<String> = info
                     .column_names
@@ -675,6 +1018,173 @@ impl RustoraSession {
         Err(RustoraError::TableNotFound(name.to_string()))
     }
 
+    /// Rename several columns at once from an `old -> new` mapping.
+    /// All renames land in a single statement, so either every column is renamed or none is.
+    /// Fails if a source column is missing, a target name is empty or contains `"`,
+    /// or the resulting column names are not unique (DuckDB names are case-insensitive).
+    pub fn rename_columns(
+        &mut self,
+        name: &str,
+        mapping: &HashMap<String, String>,
+    ) -> Result<String> {
+        self.ensure_writable("rename_columns")?;
+        if mapping.is_empty() {
+            return Err(RustoraError::Session("No columns to rename".to_string()));
+        }
+        let info = self.dataset_info(name)?;
+        for (old, new) in mapping {
+            if !info.column_names.contains(old) {
+                return Err(RustoraError::ColumnNotFound(old.clone()));
+            }
+            if new.trim().is_empty() || new.contains('"') {
+                return Err(RustoraError::Session(format!(
+                    "Invalid column name: '{}'",
+                    new
+                )));
+            }
+        }
+
+        let final_names: Vec<&str> = info
+            .column_names
+            .iter()
+            .map(|c| mapping.get(c).unwrap_or(c).as_str())
+            .collect();
+        for (i, n) in final_names.iter().enumerate() {
+            if final_names[..i]
+                .iter()
+                .any(|prev| prev.eq_ignore_ascii_case(n))
+            {
+                return Err(RustoraError::Session(format!(
+                    "Renaming would produce duplicate column '{}'",
+                    n
+                )));
+            }
+        }
+
+        let mut pairs: Vec<(String, String)> = mapping
+            .iter()
+            .map(|(o, n)| (o.clone(), n.clone()))
+            .collect();
+        pairs.sort();
+        let step = TransformStep::RenameColumns { mapping: pairs };
+
+        if info.persistent {
+            let storage = self.storage()?;
+            let select_exprs: Vec<String> = info
+                .column_names
+                .iter()
+                .zip(final_names.iter())
+                .map(|(c, n)| format!("\"{}\" AS \"{}\"", c, n))
+                .collect();
+            let sql = format!("SELECT {} FROM \"{}\"", select_exprs.join(", "), name);
+            let result_name = format!("{}_renamed_{}", name, self.next_counter());
+            storage.execute_sql_to_table(&sql, &result_name)?;
+            self.record_step(name, &result_name, step);
+            return Ok(result_name);
+        }
+
+        let lf = self
+            .transient
+            .get(name)
+            .ok_or(RustoraError::TableNotFound(name.to_string()))?;
+        let exprs: Vec<Expr> = info
+            .column_names
+            .iter()
+            .zip(final_names.iter())
+            .map(|(c, n)| col(c.as_str()).alias(*n))
+            .collect();
+        let renamed = lf.clone().select(exprs);
+        let new_name = format!("{}_renamed_{}", name, self.next_counter());
+        self.transient.insert(new_name.clone(), renamed);
+        self.record_step(name, &new_name, step);
+        Ok(new_name)
+    }
+
+    /// Append a 1-based sequential index column.
+    /// With `order_by`, numbering follows `row_number() OVER (ORDER BY ...)` and the
+    /// result is sorted the same way; otherwise rows are numbered in their current order.
+    pub fn add_row_number(
+        &mut self,
+        name: &str,
+        column_name: &str,
+        order_by: Option<&[&str]>,
+    ) -> Result<String> {
+        self.ensure_writable("add_row_number")?;
+        if column_name.is_empty() {
+            return Err(RustoraError::Session(
+                "Row number column name cannot be empty".to_string(),
+            ));
+        }
+        let info = self.dataset_info(name)?;
+        if info
+            .column_names
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(column_name))
+        {
+            return Err(RustoraError::Session(format!(
+                "Column '{}' already exists in '{}'",
+                column_name, name
+            )));
+        }
+        let order_cols: Vec<&str> = order_by.unwrap_or(&[]).to_vec();
+        for c in &order_cols {
+            if !info.column_names.iter().any(|existing| existing == c) {
+                return Err(RustoraError::ColumnNotFound(c.to_string()));
+            }
+        }
+        let step = TransformStep::AddRowNumber {
+            column: column_name.to_string(),
+            order_by: order_cols.iter().map(|c| c.to_string()).collect(),
+        };
+
+        if info.persistent {
+            let storage = self.storage()?;
+            let sql = if order_cols.is_empty() {
+                format!(
+                    "SELECT *, row_number() OVER () AS \"{}\" FROM \"{}\"",
+                    column_name, name
+                )
+            } else {
+                let order = order_cols
+                    .iter()
+                    .map(|c| format!("\"{}\"", c))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "SELECT *, row_number() OVER (ORDER BY {order}) AS \"{col}\" \
+                     FROM \"{table}\" ORDER BY \"{col}\"",
+                    order = order,
+                    col = column_name,
+                    table = name,
+                )
+            };
+            let result_name = format!("{}_rownum_{}", name, self.next_counter());
+            storage.execute_sql_to_table(&sql, &result_name)?;
+            self.record_step(name, &result_name, step);
+            return Ok(result_name);
+        }
+
+        let lf = self
+            .transient
+            .get(name)
+            .ok_or(RustoraError::TableNotFound(name.to_string()))?
+            .clone();
+        let lf = if order_cols.is_empty() {
+            lf
+        } else {
+            let by: Vec<PlSmallStr> = order_cols.iter().map(|c| PlSmallStr::from(*c)).collect();
+            lf.sort(by, SortMultipleOptions::default())
+        };
+        // with_row_index prepends the column; move it to the end to match the DuckDB layout.
+        let indexed = lf
+            .with_row_index(column_name, Some(1))
+            .select([col("*").exclude([column_name]), col(column_name)]);
+        let new_name = format!("{}_rownum_{}", name, self.next_counter());
+        self.transient.insert(new_name.clone(), indexed);
+        self.record_step(name, &new_name, step);
+        Ok(new_name)
+    }
+
     // -----------------------------------------------------------------------
     // Pivot / Unpivot
     // -----------------------------------------------------------------------
@@ -687,8 +1197,9 @@ impl RustoraSession {
         value_col: &str,
         agg: &str,
     ) -> Result<String> {
+        self.ensure_writable("pivot")?;
         let storage = self.storage.as_ref().ok_or(RustoraError::NoProjectOpen)?;
-        if !storage.list_tables()?.contains(&name.to_string()) {
+        if !storage.table_exists(name)? {
             return Err(RustoraError::TableNotFound(name.to_string()));
         }
         let idx = index_cols
@@ -726,8 +1237,9 @@ impl RustoraSession {
         var_name: &str,
         value_name: &str,
     ) -> Result<String> {
+        self.ensure_writable("unpivot")?;
         let storage = self.storage.as_ref().ok_or(RustoraError::NoProjectOpen)?;
-        if !storage.list_tables()?.contains(&name.to_string()) {
+        if !storage.table_exists(name)? {
             return Err(RustoraError::TableNotFound(name.to_string()));
         }
         let val_cols = value_cols
@@ -766,6 +1278,7 @@ impl RustoraSession {
         right_col: &str,
         join_type: &str,
     ) -> Result<String> {
+        self.ensure_writable("merge")?;
         let storage = self.storage.as_ref().ok_or(RustoraError::NoProjectOpen)?;
         let join_keyword = match join_type {
             "inner" => "INNER JOIN",
@@ -794,6 +1307,7 @@ impl RustoraSession {
     }
 
     pub fn append_datasets(&mut self, tables: &[&str]) -> Result<String> {
+        self.ensure_writable("append")?;
         let storage = self.storage.as_ref().ok_or(RustoraError::NoProjectOpen)?;
         if tables.is_empty() {
             return Err(RustoraError::Session(
@@ -817,10 +1331,99 @@ impl RustoraSession {
         Ok(result_name)
     }
 
+    /// Stack two tables side by side, pairing rows by position.
+    /// Both tables must have the same row count. Column names from `right` that
+    /// collide with `left` are an error unless `suffix` is given, in which case
+    /// the colliding right-hand columns are renamed to `{column}{suffix}`.
+    pub fn hstack(&mut self, left: &str, right: &str, suffix: Option<&str>) -> Result<String> {
+        self.ensure_writable("hstack")?;
+        let storage = self.storage.as_ref().ok_or(RustoraError::NoProjectOpen)?;
+        for t in [left, right] {
+            if !storage.table_exists(t)? {
+                return Err(RustoraError::TableNotFound(t.to_string()));
+            }
+        }
+
+        let left_info = storage.table_info(left)?;
+        let right_info = storage.table_info(right)?;
+        if left_info.row_count != right_info.row_count {
+            return Err(RustoraError::Session(format!(
+                "Cannot stack '{}' ({} rows) beside '{}' ({} rows): row counts differ",
+                left, left_info.row_count, right, right_info.row_count
+            )));
+        }
+
+        let mut output_names: Vec<String> = left_info.column_names.clone();
+        let mut select_exprs: Vec<String> = left_info
+            .column_names
+            .iter()
+            .map(|c| format!("l.\"{}\"", c))
+            .collect();
+        for c in &right_info.column_names {
+            let out_name = if left_info
+                .column_names
+                .iter()
+                .any(|l| l.eq_ignore_ascii_case(c))
+            {
+                match suffix {
+                    Some(sfx) if !sfx.is_empty() => format!("{}{}", c, sfx),
+                    _ => {
+                        return Err(RustoraError::Session(format!(
+                            "Column '{}' exists in both '{}' and '{}'; provide a suffix to disambiguate",
+                            c, left, right
+                        )))
+                    }
+                }
+            } else {
+                c.clone()
+            };
+            if output_names
+                .iter()
+                .any(|o| o.eq_ignore_ascii_case(&out_name))
+            {
+                return Err(RustoraError::Session(format!(
+                    "Column '{}' would appear twice in the stacked result",
+                    out_name
+                )));
+            }
+            select_exprs.push(format!("r.\"{}\" AS \"{}\"", c, out_name));
+            output_names.push(out_name);
+        }
+
+        let sql = format!(
+            "WITH l AS (SELECT *, row_number() OVER () AS \"_rustora_pos\" FROM \"{left}\"), \
+                  r AS (SELECT *, row_number() OVER () AS \"_rustora_pos\" FROM \"{right}\") \
+             SELECT {cols} FROM l JOIN r ON l.\"_rustora_pos\" = r.\"_rustora_pos\" \
+             ORDER BY l.\"_rustora_pos\"",
+            left = left,
+            right = right,
+            cols = select_exprs.join(", "),
+        );
+        let result_name = format!("hstacked_{}", self.next_counter());
+        storage.execute_sql_to_table(&sql, &result_name)?;
+        self.record_step(
+            left,
+            &result_name,
+            TransformStep::HStack {
+                right_table: right.to_string(),
+                suffix: suffix.map(|s| s.to_string()),
+            },
+        );
+        Ok(result_name)
+    }
+
     // -----------------------------------------------------------------------
     // Preview / Import with Options
     // -----------------------------------------------------------------------
 
+    /// Read the first `limit` rows of a file as Arrow IPC bytes without importing it.
+    /// No table or transient dataset is created, and CSV type detection only samples
+    /// the head of the file, so this stays fast on multi-gigabyte inputs.
+    pub fn quick_preview_file(&self, file_path: &str, limit: u32) -> Result<Vec<u8>> {
+        let storage = self.storage.as_ref().ok_or(RustoraError::NoProjectOpen)?;
+        storage.preview_file(file_path, None, limit as u64)
+    }
+
     pub fn preview_file(
         &self,
         file_path: &str,
@@ -834,8 +1437,9 @@ impl RustoraSession {
             delimiter,
             has_header,
             skip_rows,
+            ..Default::default()
         };
-        storage.preview_file(file_path, &options, limit as u64)
+        storage.preview_file(file_path, Some(&options), limit as u64)
     }
 
     pub fn import_file_with_options(
@@ -846,40 +1450,76 @@ impl RustoraSession {
         has_header: bool,
         skip_rows: u32,
     ) -> Result<String> {
+        self.ensure_writable("import")?;
         let storage = self.storage.as_ref().ok_or(RustoraError::NoProjectOpen)?;
         let name = match table_name {
             Some(n) if !n.is_empty() => n.to_string(),
             _ => self.generate_name(file_path),
         };
+        let name = sanitize_table_name(&name);
+        let existed = self.find_dataset_kind(&name)?.is_some();
         let ext = Path::new(file_path)
             .extension()
             .and_then(|s| s.to_str())
             .unwrap_or("")
             .to_lowercase();
-        match ext.as_str() {
+        let name = match ext.as_str() {
             "csv" | "tsv" => {
                 let options = CsvImportOptions {
                     delimiter,
                     has_header,
                     skip_rows,
+                    ..Default::default()
                 };
-                storage.import_csv_with_options(file_path, &name, &options)?;
-            }
-            _ => {
-                storage.import_file(file_path, &name)?;
+                storage.import_csv_with_options(file_path, &name, &options)?
             }
+            _ => storage.import_file(file_path, &name)?,
+        };
+        self.record_source_step(&name, file_path);
+        self.notify_written(&name, existed);
+        Ok(name)
+    }
+
+    /// Import a CSV with full control over dialect and ragged-row handling
+    /// (fail fast, skip malformed rows, or NULL-pad short rows).
+    pub fn import_csv_with_options(
+        &mut self,
+        file_path: &str,
+        table_name: Option<&str>,
+        options: &CsvImportOptions,
+    ) -> Result<String> {
+        self.ensure_writable("import")?;
+        let storage = self.storage.as_ref().ok_or(RustoraError::NoProjectOpen)?;
+        if !Path::new(file_path).exists() {
+            return Err(RustoraError::FileNotFound(file_path.to_string()));
+        }
+        let name = match table_name {
+            Some(n) if !n.is_empty() => n.to_string(),
+            _ => self.generate_name(file_path),
         };
+        let name = sanitize_table_name(&name);
+        let existed = self.find_dataset_kind(&name)?.is_some();
+        info!(file_path, table = %name, ragged_rows = ?options.ragged_rows, "importing CSV with options");
+        let name = storage.import_csv_with_options(file_path, &name, options)?;
         self.record_source_step(&name, file_path);
+        self.notify_written(&name, existed);
         Ok(name)
     }
 
+    /// Lines dropped by the most recent CSV import in
+    /// [`RaggedRowMode::SkipBadRows`](crate::storage::RaggedRowMode::SkipBadRows) mode.
+    pub fn csv_rejects(&self) -> Result<Vec<CsvRejectedRow>> {
+        self.storage()?.csv_rejects()
+    }
+
     // -----------------------------------------------------------------------
     // Chart / Aggregation
     // -----------------------------------------------------------------------
 
     /// Aggregate data for chart visualization.
     /// Returns up to `limit` groups, sorted by the group column.
-    /// `agg_type` can be: "count", "sum", "avg", "min", "max"
+    /// `agg_type` must be one of "count", "sum", "avg", "min", "max"; anything
+    /// else is rejected before SQL is built.
     pub fn aggregate_for_chart(
         &self,
         name: &str,
@@ -890,30 +1530,38 @@ impl RustoraSession {
     ) -> Result<Vec<u8>> {
         let storage = self.storage.as_ref().ok_or(RustoraError::NoProjectOpen)?;
 
-        if !storage.list_tables()?.contains(&name.to_string()) {
+        if !storage.table_exists(name)? {
             return Err(RustoraError::TableNotFound(name.to_string()));
         }
 
-        let agg_expr = match (agg_type, value_col) {
+        let agg_expr = match (agg_type.to_lowercase().as_str(), value_col) {
             ("count", _) => "COUNT(*)".to_string(),
-            (agg, Some(vc)) => format!("{}(\"{}\")", agg.to_uppercase(), vc),
-            (agg, None) => {
+            (agg @ ("sum" | "avg" | "min" | "max"), Some(vc)) => {
+                format!("{}({})", agg.to_uppercase(), quote_ident(vc))
+            }
+            (agg @ ("sum" | "avg" | "min" | "max"), None) => {
                 return Err(RustoraError::Session(format!(
                     "Aggregation '{}' requires a value column",
                     agg
                 )))
             }
+            (other, _) => {
+                return Err(RustoraError::Session(format!(
+                    "Unsupported aggregation: {}",
+                    other
+                )))
+            }
         };
 
         let sql = format!(
-            "SELECT \"{group}\" AS label, {agg} AS value \
-             FROM \"{table}\" \
-             GROUP BY \"{group}\" \
+            "SELECT {group} AS label, {agg} AS value \
+             FROM {table} \
+             GROUP BY {group} \
              ORDER BY value DESC \
              LIMIT {limit}",
-            group = group_col,
+            group = quote_ident(group_col),
             agg = agg_expr,
-            table = name,
+            table = quote_ident(name),
             limit = limit,
         );
 
@@ -927,13 +1575,15 @@ impl RustoraSession {
     /// Export a dataset to Parquet.
     /// For transient LazyFrames, uses streaming sink to avoid loading the full dataset into memory.
     pub fn export_to_parquet(&self, name: &str, output_path: &str) -> Result<()> {
+        self.ensure_writable("export")?;
         if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
+            if storage.table_exists(name)? {
                 return storage.export_to_parquet(name, output_path);
             }
         }
 
         if let Some(lf) = self.transient.get(name) {
+            ensure_parent_dir(output_path)?;
             lf.clone()
                 .sink_parquet(&output_path, ParquetWriteOptions::default(), None)?;
             return Ok(());
@@ -945,17 +1595,35 @@ impl RustoraSession {
     /// Export a dataset to CSV.
     /// For transient LazyFrames, uses streaming sink to avoid loading the full dataset into memory.
     pub fn export_to_csv(&self, name: &str, output_path: &str) -> Result<()> {
+        self.export_to_csv_with_options(name, output_path, &CsvExportOptions::default())
+    }
+
+    /// Export a dataset to CSV with fixed decimal places and a custom NULL string.
+    /// Only the written file is formatted; the dataset itself is unchanged.
+    pub fn export_to_csv_with_options(
+        &self,
+        name: &str,
+        output_path: &str,
+        options: &CsvExportOptions,
+    ) -> Result<()> {
+        self.ensure_writable("export")?;
         if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
-                return storage.export_to_csv(name, output_path);
+            if storage.table_exists(name)? {
+                return storage.export_to_csv_with_options(name, output_path, options);
             }
         }
 
         if let Some(lf) = self.transient.get(name) {
+            ensure_parent_dir(output_path)?;
             lf.clone().sink_csv(
-                &output_path,
+                output_path,
                 CsvWriterOptions {
                     include_header: true,
+                    serialize_options: SerializeOptions {
+                        float_precision: options.decimal_places.map(|p| p as usize),
+                        null: options.null_value.clone(),
+                        ..Default::default()
+                    },
                     ..Default::default()
                 },
                 None,
@@ -966,25 +1634,100 @@ impl RustoraSession {
         Err(RustoraError::TableNotFound(name.to_string()))
     }
 
+    /// Export a dataset to an Arrow IPC file without materializing it in memory.
+    /// DuckDB tables are written chunk by chunk; transient LazyFrames use a streaming sink.
+    pub fn export_to_ipc_file(&self, name: &str, output_path: &str) -> Result<()> {
+        self.ensure_writable("export")?;
+        if let Some(storage) = &self.storage {
+            if storage.table_exists(name)? {
+                return storage.export_to_ipc_file(name, output_path, IPC_EXPORT_CHUNK_ROWS);
+            }
+        }
+
+        if let Some(lf) = self.transient.get(name) {
+            ensure_parent_dir(output_path)?;
+            lf.clone()
+                .sink_ipc(output_path, IpcWriterOptions::default(), None)?;
+            return Ok(());
+        }
+
+        Err(RustoraError::TableNotFound(name.to_string()))
+    }
+
     // -----------------------------------------------------------------------
     // Remove / Clean up
     // -----------------------------------------------------------------------
 
     /// Remove a dataset (drops DuckDB table or removes transient LazyFrame).
     pub fn remove_dataset(&mut self, name: &str) -> Result<bool> {
+        self.ensure_writable("remove_dataset")?;
+        let mut removed = false;
         if let Some(storage) = &self.storage {
-            if storage.list_tables()?.contains(&name.to_string()) {
+            if storage.table_exists(name)? {
                 storage.drop_table(name)?;
-                return Ok(true);
+                removed = true;
+            }
+        }
+        if !removed {
+            removed = self.transient.remove(name).is_some();
+        }
+
+        if removed {
+            self.notify(DatasetEvent::Dropped {
+                name: name.to_string(),
+            });
+        }
+        Ok(removed)
+    }
+
+    /// Rename a dataset (DuckDB table or transient LazyFrame), carrying its
+    /// transform history over to the new name.
+    pub fn rename_dataset(&mut self, name: &str, new_name: &str) -> Result<()> {
+        self.ensure_writable("rename_dataset")?;
+        if new_name.is_empty() {
+            return Err(RustoraError::Session("new dataset name is empty".to_string()));
+        }
+        if self.find_dataset_kind(new_name)?.is_some() {
+            return Err(RustoraError::Session(format!(
+                "dataset '{}' already exists",
+                new_name
+            )));
+        }
+
+        match self.find_dataset_kind(name)? {
+            Some(DatasetKind::Persistent) => self.storage()?.rename_table(name, new_name)?,
+            Some(DatasetKind::Transient) => {
+                if let Some(lf) = self.transient.remove(name) {
+                    self.transient.insert(new_name.to_string(), lf);
+                }
+            }
+            None => return Err(RustoraError::TableNotFound(name.to_string())),
+        }
+
+        if let Some(history) = self.histories.remove(name) {
+            if let Some(storage) = &self.storage {
+                if let Ok(json) = serde_json::to_string(history.entries()) {
+                    let _ = storage.save_step_history_json(new_name, &json);
+                }
+                let _ = storage.delete_step_history(name);
             }
+            self.histories.insert(new_name.to_string(), history);
         }
 
-        Ok(self.transient.remove(name).is_some())
+        self.notify(DatasetEvent::Renamed {
+            from: name.to_string(),
+            to: new_name.to_string(),
+        });
+        Ok(())
     }
 
     /// Register an existing LazyFrame as a transient dataset.
-    pub fn register_lazy_frame(&mut self, name: &str, lf: LazyFrame) {
+    pub fn register_lazy_frame(&mut self, name: &str, lf: LazyFrame) -> Result<()> {
+        self.ensure_writable("register_lazy_frame")?;
+        let existed = self.find_dataset_kind(name)?.is_some();
         self.transient.insert(name.to_string(), lf);
+        self.notify_written(name, existed);
+        Ok(())
     }
 
     // -----------------------------------------------------------------------
@@ -1018,6 +1761,8 @@ impl Default for RustoraSession {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::aggregate::{AggFunction, GroupValue};
+    use crate::filter::{FilterCondition, FilterIssueKind, FilterLogic, FilterOperator};
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -1032,6 +1777,10 @@ mod tests {
         file
     }
 
+    fn ipc_to_df(bytes: &[u8]) -> DataFrame {
+        IpcStreamReader::new(Cursor::new(bytes)).finish().unwrap()
+    }
+
     #[test]
     fn test_import_and_preview() {
         let csv = create_test_csv();
@@ -1072,6 +1821,22 @@ mod tests {
         assert!(info.column_names.contains(&"name".to_string()));
     }
 
+    #[test]
+    fn test_preview_file_limits_rows() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let session = RustoraSession::new();
+        let before = session.list_datasets();
+
+        let df = ipc_to_df(&session.quick_preview_file(path, 3).unwrap());
+        assert_eq!(df.height(), 3);
+        assert_eq!(df.width(), 4);
+
+        // Previewing must not register anything.
+        assert_eq!(session.list_datasets(), before);
+    }
+
     #[test]
     fn test_chunked_ipc() {
         let csv = create_test_csv();
@@ -1118,6 +1883,26 @@ mod tests {
         assert!(count > 0);
     }
 
+    #[test]
+    fn test_referenced_tables_join() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.import_file(path, Some("ref_people")).unwrap();
+        session.import_file(path, Some("ref_scores")).unwrap();
+
+        let tables = session
+            .referenced_tables(
+                "WITH top AS (SELECT name FROM ref_scores WHERE score > 80) \
+                 SELECT p.name, p.city FROM ref_people p \
+                 JOIN top ON p.name = top.name \
+                 WHERE p.age > (SELECT MIN(age) FROM ref_people)",
+            )
+            .unwrap();
+        assert_eq!(tables, vec!["ref_people", "ref_scores"]);
+    }
+
     #[test]
     fn test_sort_dataset_duckdb() {
         let csv = create_test_csv();
@@ -1151,6 +1936,31 @@ mod tests {
         assert!(content.contains("Alice"));
     }
 
+    #[test]
+    fn test_export_creates_missing_directory() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        let name = session.import_file(path, Some("export_dir_test")).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let results = dir.path().join("results").join("nested");
+        assert!(!results.exists());
+
+        let csv_out = results.join("out.csv");
+        session
+            .export_to_csv(&name, csv_out.to_str().unwrap())
+            .unwrap();
+        assert!(std::fs::read_to_string(&csv_out).unwrap().contains("Alice"));
+
+        let parquet_out = results.join("out.parquet");
+        session
+            .export_to_parquet(&name, parquet_out.to_str().unwrap())
+            .unwrap();
+        assert!(parquet_out.exists());
+    }
+
     #[test]
     fn test_remove_dataset() {
         let csv = create_test_csv();
@@ -1166,6 +1976,439 @@ mod tests {
         assert!(!session.list_datasets().contains(&"remove_me".to_string()));
     }
 
+    #[test]
+    fn test_dataset_exists_and_kind() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.import_file(path, Some("exists_test")).unwrap();
+        let scanned = session.scan_file(path).unwrap();
+
+        assert!(session.dataset_exists("exists_test"));
+        assert!(!session.dataset_exists("exsits_test"));
+        assert_eq!(
+            session.dataset_kind("exists_test"),
+            Some(DatasetKind::Persistent)
+        );
+        assert_eq!(session.dataset_kind(&scanned), Some(DatasetKind::Transient));
+        assert_eq!(session.dataset_kind("exsits_test"), None);
+    }
+
+    #[test]
+    fn test_import_hive_partitioned() {
+        let root = tempfile::tempdir().unwrap();
+        let root_path = root.path().to_str().unwrap().replace('\\', "/");
+        let mut session = RustoraSession::new();
+        for (year, month, value) in [(2023, "12", 1), (2024, "01", 2), (2024, "02", 3)] {
+            let part = session
+                .execute_sql(&format!("SELECT {} AS value", value))
+                .unwrap();
+            session
+                .export_to_parquet(
+                    &part,
+                    &format!("{}/year={}/month={}/part-0.parquet", root_path, year, month),
+                )
+                .unwrap();
+            session.remove_dataset(&part).unwrap();
+        }
+
+        let name = session
+            .import_hive_partitioned(&root_path, Some("lake"))
+            .unwrap();
+        let info = session.dataset_info(&name).unwrap();
+        assert!(info.column_names.contains(&"year".to_string()));
+        assert!(info.column_names.contains(&"month".to_string()));
+
+        let df = ipc_to_df(
+            &session
+                .execute_sql_to_ipc("SELECT count(*) AS n FROM lake WHERE year = 2024")
+                .unwrap(),
+        );
+        assert_eq!(df.column("n").unwrap().i64().unwrap().get(0), Some(2));
+
+        let empty = tempfile::tempdir().unwrap();
+        let err = session
+            .import_hive_partitioned(empty.path().to_str().unwrap(), Some("empty_lake"))
+            .unwrap_err();
+        assert!(matches!(err, RustoraError::FileNotFound(_)));
+
+        // Re-importing under an unsanitized name overwrites the same table.
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        session.on_change(move |event| sink.lock().unwrap().push(event.clone()));
+        session.import_hive_partitioned(&root_path, Some("lake-2")).unwrap();
+        session.import_hive_partitioned(&root_path, Some("lake-2")).unwrap();
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                DatasetEvent::Created { name: "lake_2".to_string() },
+                DatasetEvent::Modified { name: "lake_2".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_aggregate_typed_rows() {
+        let mut csv = NamedTempFile::with_suffix(".csv").unwrap();
+        writeln!(csv, "name,city,score").unwrap();
+        writeln!(csv, "Alice,Boston,90.0").unwrap();
+        writeln!(csv, "Bob,Chicago,70.0").unwrap();
+        writeln!(csv, "Carol,Boston,80.0").unwrap();
+        writeln!(csv, "Dan,Chicago,60.0").unwrap();
+        writeln!(csv, "Erin,Chicago,80.0").unwrap();
+
+        let mut session = RustoraSession::new();
+        session
+            .import_file(csv.path().to_str().unwrap(), Some("scores"))
+            .unwrap();
+
+        let aggs = vec![
+            AggSpec {
+                function: AggFunction::Count,
+                column: None,
+                alias: "n".to_string(),
+            },
+            AggSpec {
+                function: AggFunction::Avg,
+                column: Some("score".to_string()),
+                alias: "avg_score".to_string(),
+            },
+        ];
+        let rows = session.group_aggregate("scores", &["city"], &aggs).unwrap();
+
+        assert_eq!(
+            rows,
+            vec![
+                GroupRow {
+                    keys: vec![GroupValue::Text("Boston".to_string())],
+                    values: vec![GroupValue::Int(2), GroupValue::Float(85.0)],
+                },
+                GroupRow {
+                    keys: vec![GroupValue::Text("Chicago".to_string())],
+                    values: vec![GroupValue::Int(3), GroupValue::Float(70.0)],
+                },
+            ]
+        );
+
+        let bad = AggSpec {
+            function: AggFunction::Sum,
+            column: Some("score) FROM scores; --".to_string()),
+            alias: "x".to_string(),
+        };
+        assert!(matches!(
+            session.group_aggregate("scores", &["city"], &[bad]),
+            Err(RustoraError::ColumnNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_group_aggregate_sum_overflow_falls_back_to_float() {
+        let mut csv = NamedTempFile::with_suffix(".csv").unwrap();
+        writeln!(csv, "grp,amount").unwrap();
+        writeln!(csv, "a,9223372036854775807").unwrap();
+        writeln!(csv, "a,10").unwrap();
+        writeln!(csv, "b,5").unwrap();
+        writeln!(csv, "b,7").unwrap();
+
+        let mut session = RustoraSession::new();
+        session
+            .import_file(csv.path().to_str().unwrap(), Some("big"))
+            .unwrap();
+
+        let aggs = vec![AggSpec {
+            function: AggFunction::Sum,
+            column: Some("amount".to_string()),
+            alias: "total".to_string(),
+        }];
+        let rows = session.group_aggregate("big", &["grp"], &aggs).unwrap();
+        assert_eq!(rows[0].values, vec![GroupValue::Float(9223372036854775817.0)]);
+        assert_eq!(rows[1].values, vec![GroupValue::Int(12)]);
+
+        let lf = df! { "grp" => ["a"], "amount" => [1i64] }.unwrap().lazy();
+        session.register_lazy_frame("lazy_big", lf).unwrap();
+        assert!(matches!(
+            session.group_aggregate("lazy_big", &["grp"], &aggs),
+            Err(RustoraError::Session(_))
+        ));
+    }
+
+    #[test]
+    fn test_prefetch_chunks() {
+        let mut session = RustoraSession::new();
+        let name = session
+            .execute_sql("SELECT range AS id FROM range(100)")
+            .unwrap();
+
+        let chunks = session.prefetch_chunks(&name, &[70, 0, 30, 0], 10).unwrap();
+        assert_eq!(chunks.keys().copied().collect::<Vec<_>>(), vec![0, 30, 70]);
+        for (offset, bytes) in &chunks {
+            let ids: Vec<i64> = ipc_to_df(bytes)
+                .column("id")
+                .unwrap()
+                .i64()
+                .unwrap()
+                .into_no_null_iter()
+                .collect();
+            let expected: Vec<i64> = (*offset as i64..*offset as i64 + 10).collect();
+            assert_eq!(ids, expected);
+        }
+
+        assert!(matches!(
+            session.prefetch_chunks("missing", &[0], 10),
+            Err(RustoraError::TableNotFound(_))
+        ));
+
+        let too_many: Vec<u32> = (0..=MAX_PREFETCH_CHUNKS as u32).map(|i| i * 10).collect();
+        assert!(matches!(
+            session.prefetch_chunks(&name, &too_many, 10),
+            Err(RustoraError::Session(_))
+        ));
+        let at_cap = &too_many[..MAX_PREFETCH_CHUNKS];
+        assert_eq!(
+            session.prefetch_chunks(&name, at_cap, 10).unwrap().len(),
+            MAX_PREFETCH_CHUNKS
+        );
+    }
+
+    #[test]
+    fn test_readonly_queries_allow_select() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.import_file(path, Some("people")).unwrap();
+        session.set_readonly_queries(true);
+
+        let df = ipc_to_df(
+            &session
+                .execute_sql_to_ipc("SELECT name FROM people WHERE age > 29 ORDER BY name;")
+                .unwrap(),
+        );
+        assert_eq!(df.height(), 3);
+        assert!(!session.get_preview_ipc("people", 2).unwrap().is_empty());
+        assert_eq!(session.get_row_count("people").unwrap(), 5);
+    }
+
+    #[test]
+    fn test_readonly_queries_reject_writes() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.import_file(path, Some("people")).unwrap();
+        session.set_readonly_queries(true);
+
+        for sql in [
+            "CREATE TABLE copy AS SELECT * FROM people",
+            "DROP TABLE people",
+            "SELECT 1; DROP TABLE people",
+        ] {
+            let err = session.execute_sql_to_ipc(sql).unwrap_err();
+            assert!(matches!(err, RustoraError::ReadOnly(_)), "{sql}: {err}");
+        }
+        assert!(matches!(
+            session.execute_sql("SELECT * FROM people"),
+            Err(RustoraError::ReadOnly(_))
+        ));
+        assert!(matches!(
+            session.sort_dataset("people", &["age"], &[false]),
+            Err(RustoraError::ReadOnly(_))
+        ));
+        assert!(matches!(
+            session.remove_dataset("people"),
+            Err(RustoraError::ReadOnly(_))
+        ));
+        assert!(matches!(
+            session.import_file(path, Some("more_people")),
+            Err(RustoraError::ReadOnly(_))
+        ));
+        assert_eq!(session.list_tables().unwrap(), vec!["people".to_string()]);
+
+        session.set_readonly_queries(false);
+        session.execute_sql("SELECT * FROM people").unwrap();
+    }
+
+    #[test]
+    fn test_readonly_queries_cover_other_read_paths() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.import_file(path, Some("people")).unwrap();
+        session.set_readonly_queries(true);
+
+        let df = ipc_to_df(
+            &session
+                .aggregate_for_chart("people", "city", Some("age"), "SUM", 10)
+                .unwrap(),
+        );
+        assert_eq!(df.height(), 5);
+
+        let injected = session.aggregate_for_chart(
+            "people",
+            "city",
+            Some("age"),
+            "SUM(\"age\") FROM people; DROP TABLE people; SELECT SUM",
+            10,
+        );
+        assert!(injected.is_err());
+        assert!(session.referenced_tables("SELECT 1; DROP TABLE people").is_err());
+        assert!(matches!(
+            session.register_lazy_frame("scratch", df.lazy()),
+            Err(RustoraError::ReadOnly(_))
+        ));
+        assert_eq!(session.list_tables().unwrap(), vec!["people".to_string()]);
+    }
+
+    #[test]
+    fn test_readonly_queries_do_not_create_project_tables() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("readonly.duckdb");
+        let db_path = db_path.to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.set_readonly_queries(true);
+        assert!(matches!(
+            session.new_project(db_path),
+            Err(RustoraError::ReadOnly(_))
+        ));
+        assert!(!Path::new(db_path).exists());
+        assert!(matches!(
+            session.open_project(db_path),
+            Err(RustoraError::ReadOnly(_))
+        ));
+        assert!(!Path::new(db_path).exists());
+
+        drop(DuckStorage::open(db_path).unwrap());
+        session.open_project(db_path).unwrap();
+        let steps_exists = |session: &RustoraSession| -> bool {
+            session
+                .storage()
+                .unwrap()
+                .query_to_ipc("SELECT * FROM _rustora_steps")
+                .is_ok()
+        };
+        assert!(!steps_exists(&session));
+
+        session.set_readonly_queries(false);
+        assert!(steps_exists(&session));
+    }
+
+    #[test]
+    fn test_on_change_events() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut session = RustoraSession::new();
+        let sink = Arc::clone(&events);
+        session.on_change(move |event| sink.lock().unwrap().push(event.clone()));
+
+        session.import_file(path, Some("observed")).unwrap();
+        session.import_file(path, Some("observed")).unwrap();
+        session.rename_dataset("observed", "observed_renamed").unwrap();
+        session.remove_dataset("observed_renamed").unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            *events,
+            vec![
+                DatasetEvent::Created { name: "observed".to_string() },
+                DatasetEvent::Modified { name: "observed".to_string() },
+                DatasetEvent::Renamed {
+                    from: "observed".to_string(),
+                    to: "observed_renamed".to_string(),
+                },
+                DatasetEvent::Dropped { name: "observed_renamed".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_and_project_swap_events() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("swap.duckdb");
+        let db_path = db_path.to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.new_project(db_path).unwrap();
+        session.import_file(path, Some("stored")).unwrap();
+        let scanned = session.scan_file(path).unwrap();
+
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        session.on_change(move |event| sink.lock().unwrap().push(event.clone()));
+
+        let filtered = session.filter_dataset(&scanned, col("age").gt(lit(28))).unwrap();
+        session.filter_dataset(&scanned, col("age").gt(lit(30))).unwrap();
+        session.open_project(db_path).unwrap();
+
+        let mut before = vec![filtered.clone(), scanned.clone(), "stored".to_string()];
+        before.sort();
+        let mut expected = vec![
+            DatasetEvent::Created { name: filtered.clone() },
+            DatasetEvent::Modified { name: filtered },
+        ];
+        expected.extend(before.into_iter().map(|name| DatasetEvent::Dropped { name }));
+        expected.push(DatasetEvent::Created { name: "stored".to_string() });
+        assert_eq!(*events.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_import_events_use_sanitized_name() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut session = RustoraSession::new();
+        let sink = Arc::clone(&events);
+        session.on_change(move |event| sink.lock().unwrap().push(event.clone()));
+
+        assert_eq!(session.import_file(path, Some("my-data")).unwrap(), "my_data");
+        session.import_file(path, Some("my-data")).unwrap();
+        session
+            .import_file_with_options(path, Some("my-data"), b',', true, 0)
+            .unwrap();
+        session
+            .import_csv_with_options(path, Some("my-data"), &CsvImportOptions::default())
+            .unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            *events,
+            vec![
+                DatasetEvent::Created { name: "my_data".to_string() },
+                DatasetEvent::Modified { name: "my_data".to_string() },
+                DatasetEvent::Modified { name: "my_data".to_string() },
+                DatasetEvent::Modified { name: "my_data".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rename_dataset_moves_step_history() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.import_file(path, Some("before")).unwrap();
+        session.rename_dataset("before", "after").unwrap();
+
+        assert!(!session.get_history("after").is_empty());
+        let stored: Vec<String> = session
+            .storage()
+            .unwrap()
+            .load_all_step_histories()
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(stored, vec!["after".to_string()]);
+    }
+
     #[test]
     fn test_list_datasets_combined() {
         let csv = create_test_csv();
@@ -1225,6 +2468,42 @@ mod tests {
         assert!(!ipc.is_empty());
     }
 
+    #[test]
+    fn test_null_safe_not_equals_keeps_nulls() {
+        let mut csv = NamedTempFile::with_suffix(".csv").unwrap();
+        writeln!(csv, "name,city").unwrap();
+        writeln!(csv, "Alice,Boston").unwrap();
+        writeln!(csv, "Bob,").unwrap();
+        writeln!(csv, "Charlie,Chicago").unwrap();
+        writeln!(csv, "Diana,").unwrap();
+        let path = csv.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.import_file(path, Some("nulls_test")).unwrap();
+
+        let not_boston = |operator: FilterOperator| FilterSpec {
+            conditions: vec![FilterCondition {
+                column: "city".to_string(),
+                operator,
+                value: "Boston".to_string(),
+            }],
+            logic: FilterLogic::And,
+        };
+
+        let standard = session
+            .filter_dataset_structured("nulls_test", &not_boston(FilterOperator::NotEquals))
+            .unwrap();
+        assert_eq!(session.get_row_count(&standard).unwrap(), 1);
+
+        let null_safe = session
+            .filter_dataset_structured(
+                "nulls_test",
+                &not_boston(FilterOperator::NullSafeNotEquals),
+            )
+            .unwrap();
+        assert_eq!(session.get_row_count(&null_safe).unwrap(), 3);
+    }
+
     #[test]
     fn test_group_by() {
         let csv = create_test_csv();
@@ -1261,6 +2540,106 @@ mod tests {
         assert_eq!(info.num_columns, 5);
     }
 
+    #[test]
+    fn test_add_row_number_sequential() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.import_file(path, Some("rownum_test")).unwrap();
+
+        assert!(session
+            .add_row_number("rownum_test", "age", None)
+            .is_err());
+        // DuckDB identifiers are case-insensitive, so "AGE" collides as well.
+        assert!(session
+            .add_row_number("rownum_test", "AGE", None)
+            .is_err());
+
+        let result = session
+            .add_row_number("rownum_test", "idx", Some(&["age"]))
+            .unwrap();
+        let df = ipc_to_df(&session.get_preview_ipc(&result, 10).unwrap());
+        assert_eq!(df.get_column_names().last().unwrap().as_str(), "idx");
+
+        let idx: Vec<i64> = df
+            .column("idx")
+            .unwrap()
+            .cast(&DataType::Int64)
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert_eq!(idx, vec![1, 2, 3, 4, 5]);
+
+        // Youngest first under ORDER BY age.
+        let first_name = df.column("name").unwrap().str().unwrap().get(0);
+        assert_eq!(first_name, Some("Bob"));
+    }
+
+    #[test]
+    fn test_select_columns_reordered() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.import_file(path, Some("select_test")).unwrap();
+
+        let result = session
+            .select_columns("select_test", &["score", "name"])
+            .unwrap();
+        let info = session.dataset_info(&result).unwrap();
+        assert_eq!(info.column_names, vec!["score", "name"]);
+        assert_eq!(session.get_row_count(&result).unwrap(), 5);
+
+        let missing = session.select_columns("select_test", &["name", "salary"]);
+        assert!(matches!(missing, Err(RustoraError::ColumnNotFound(c)) if c == "salary"));
+
+        let scanned = session.scan_file(path).unwrap();
+        let transient = session.select_columns(&scanned, &["score", "name"]).unwrap();
+        let info = session.dataset_info(&transient).unwrap();
+        assert!(!info.persistent);
+        assert_eq!(info.column_names, vec!["score", "name"]);
+    }
+
+    #[test]
+    fn test_rename_columns_bulk() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.import_file(path, Some("bulk_rename")).unwrap();
+
+        let mapping: HashMap<String, String> = [
+            ("name", "full_name"),
+            ("city", "home_city"),
+            ("score", "exam_score"),
+        ]
+        .into_iter()
+        .map(|(o, n)| (o.to_string(), n.to_string()))
+        .collect();
+        let result = session.rename_columns("bulk_rename", &mapping).unwrap();
+        let info = session.dataset_info(&result).unwrap();
+        assert_eq!(
+            info.column_names,
+            vec!["full_name", "age", "home_city", "exam_score"]
+        );
+
+        let clash: HashMap<String, String> = [("name", "label"), ("city", "label")]
+            .into_iter()
+            .map(|(o, n)| (o.to_string(), n.to_string()))
+            .collect();
+        assert!(session.rename_columns("bulk_rename", &clash).is_err());
+
+        let missing: HashMap<String, String> =
+            [("salary".to_string(), "pay".to_string())].into_iter().collect();
+        assert!(matches!(
+            session.rename_columns("bulk_rename", &missing),
+            Err(RustoraError::ColumnNotFound(_))
+        ));
+    }
+
     #[test]
     fn test_summary_stats_ipc() {
         let csv = create_test_csv();
@@ -1273,6 +2652,109 @@ mod tests {
         assert!(!ipc.is_empty());
     }
 
+    #[test]
+    fn test_validate_filter_type_mismatch() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.import_file(path, Some("validate_bad")).unwrap();
+
+        let spec = FilterSpec {
+            conditions: vec![
+                FilterCondition {
+                    column: "city".to_string(),
+                    operator: FilterOperator::Equals,
+                    value: "Boston".to_string(),
+                },
+                FilterCondition {
+                    column: "age".to_string(),
+                    operator: FilterOperator::GreaterThan,
+                    value: "thirty".to_string(),
+                },
+            ],
+            logic: FilterLogic::And,
+        };
+
+        let issues = session.validate_filter("validate_bad", &spec).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].condition_index, 1);
+        assert_eq!(issues[0].column, "age");
+        assert_eq!(issues[0].kind, FilterIssueKind::TypeMismatch);
+    }
+
+    #[test]
+    fn test_validate_filter_valid_spec() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.import_file(path, Some("validate_ok")).unwrap();
+
+        let spec = FilterSpec {
+            conditions: vec![
+                FilterCondition {
+                    column: "age".to_string(),
+                    operator: FilterOperator::GreaterThanOrEqual,
+                    value: "28".to_string(),
+                },
+                FilterCondition {
+                    column: "name".to_string(),
+                    operator: FilterOperator::StartsWith,
+                    value: "A".to_string(),
+                },
+                FilterCondition {
+                    column: "score".to_string(),
+                    operator: FilterOperator::IsNotNull,
+                    value: String::new(),
+                },
+            ],
+            logic: FilterLogic::Or,
+        };
+
+        let issues = session.validate_filter("validate_ok", &spec).unwrap();
+        assert!(issues.is_empty(), "unexpected issues: {:?}", issues);
+    }
+
+    #[test]
+    fn test_hstack_equal_length() {
+        let csv = create_test_csv();
+        let path = csv.path().to_str().unwrap();
+
+        let mut features = NamedTempFile::with_suffix(".csv").unwrap();
+        writeln!(features, "name_len,score").unwrap();
+        for row in ["5,0.1", "3,0.2", "7,0.3", "5,0.4", "3,0.5"] {
+            writeln!(features, "{}", row).unwrap();
+        }
+        let features_path = features.path().to_str().unwrap();
+
+        let mut session = RustoraSession::new();
+        session.import_file(path, Some("hs_left")).unwrap();
+        session.import_file(features_path, Some("hs_right")).unwrap();
+
+        // "score" exists on both sides, so a suffix is required.
+        assert!(session.hstack("hs_left", "hs_right", None).is_err());
+
+        let stacked = session
+            .hstack("hs_left", "hs_right", Some("_feat"))
+            .unwrap();
+        let info = session.dataset_info(&stacked).unwrap();
+        assert_eq!(
+            info.column_names,
+            vec!["name", "age", "city", "score", "name_len", "score_feat"]
+        );
+        assert_eq!(session.get_row_count(&stacked).unwrap(), 5);
+
+        // Names differing only in case still clash in DuckDB.
+        let upper = session
+            .execute_sql("SELECT score AS \"SCORE\" FROM hs_right")
+            .unwrap();
+        assert!(session.hstack("hs_left", &upper, None).is_err());
+        let stacked = session.hstack("hs_left", &upper, Some("_feat")).unwrap();
+        let info = session.dataset_info(&stacked).unwrap();
+        assert_eq!(info.column_names.last().unwrap(), "SCORE_feat");
+    }
+
     #[test]
     fn test_unsupported_format() {
         let mut session = RustoraSession::new();
@@ -1,8 +1,15 @@
 use crate::error::{Result, RustoraError};
-use arrow_ipc::writer::StreamWriter;
+use arrow_ipc::writer::{FileWriter, StreamWriter};
+use duckdb::arrow::array::{Array, ArrayRef, GenericStringArray, OffsetSizeTrait};
+use duckdb::arrow::compute::concat_batches;
+use duckdb::arrow::datatypes::DataType;
+use duckdb::arrow::record_batch::RecordBatch;
 use duckdb::Connection;
+use std::collections::BTreeSet;
+use std::io::BufWriter;
 use std::path::Path;
-use tracing::info;
+use std::sync::Arc;
+use tracing::{info, warn};
 
 /// Metadata about a table stored in DuckDB.
 #[derive(Debug, Clone)]
@@ -14,12 +21,61 @@ pub struct TableInfo {
     pub row_count: usize,
 }
 
-/// Options for CSV import with configurable delimiter, header, and skip rows.
+/// How to treat CSV rows whose field count differs from the header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RaggedRowMode {
+    /// Abort the import on the first malformed row (DuckDB's default).
+    #[default]
+    FailFast,
+    /// Drop malformed rows and keep going (`ignore_errors=true`). The dropped
+    /// lines are captured and can be read back with [`DuckStorage::csv_rejects`].
+    SkipBadRows,
+    /// Fill missing trailing fields with NULL (`null_padding=true`).
+    NullPad,
+}
+
+impl RaggedRowMode {
+    /// Extra `read_csv` arguments for this mode, including the leading comma.
+    fn read_csv_args(self) -> &'static str {
+        match self {
+            Self::FailFast => "",
+            Self::SkipBadRows => ", ignore_errors=true",
+            Self::NullPad => ", null_padding=true",
+        }
+    }
+
+    /// Extra `read_csv` arguments that capture skipped lines for
+    /// [`DuckStorage::csv_rejects`]. Only imports use these; previews must not
+    /// overwrite the rejects of the last import.
+    fn reject_capture_args(self) -> &'static str {
+        match self {
+            Self::SkipBadRows => {
+                ", store_rejects=true, \
+                 rejects_table='_rustora_csv_rejects', rejects_scan='_rustora_csv_reject_scans'"
+            }
+            Self::FailFast | Self::NullPad => "",
+        }
+    }
+}
+
+/// A CSV line dropped by an import in [`RaggedRowMode::SkipBadRows`] mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRejectedRow {
+    /// 1-based line number in the source file.
+    pub line: u64,
+    /// The line's raw text, so it can be repaired and re-imported.
+    pub raw_line: String,
+    pub error: String,
+}
+
+/// Options for CSV import with configurable delimiter, header, skip rows,
+/// and handling of ragged rows.
 #[derive(Debug, Clone)]
 pub struct CsvImportOptions {
     pub delimiter: u8,
     pub has_header: bool,
     pub skip_rows: u32,
+    pub ragged_rows: RaggedRowMode,
 }
 
 impl Default for CsvImportOptions {
@@ -28,15 +84,31 @@ impl Default for CsvImportOptions {
             delimiter: b',',
             has_header: true,
             skip_rows: 0,
+            ragged_rows: RaggedRowMode::FailFast,
         }
     }
 }
 
+/// Options for CSV export: fixed decimal places for fractional columns and the
+/// text written for NULL values.
+#[derive(Debug, Clone, Default)]
+pub struct CsvExportOptions {
+    /// Round DOUBLE/FLOAT/DECIMAL columns to this many decimal places (None = full precision).
+    pub decimal_places: Option<u32>,
+    /// Text written in place of NULL (empty by default).
+    pub null_value: String,
+}
+
+/// Maximum number of rows DuckDB samples for CSV type detection during a preview.
+const PREVIEW_SAMPLE_ROWS: u64 = 2048;
+
 /// Persistent storage layer backed by DuckDB.
 /// Handles file import, SQL execution, and Arrow IPC serialization.
 pub struct DuckStorage {
     conn: Connection,
     db_path: String,
+    /// When set, query paths refuse text holding more than one statement.
+    readonly_queries: bool,
 }
 
 impl DuckStorage {
@@ -47,6 +119,7 @@ impl DuckStorage {
         Ok(Self {
             conn,
             db_path: db_path.to_string(),
+            readonly_queries: false,
         })
     }
 
@@ -58,6 +131,7 @@ impl DuckStorage {
         Ok(Self {
             conn,
             db_path: ":memory:".to_string(),
+            readonly_queries: false,
         })
     }
 
@@ -75,6 +149,12 @@ impl DuckStorage {
         &self.db_path
     }
 
+    /// Make [`query_to_ipc`](Self::query_to_ipc) reject multi-statement text, so
+    /// no read path can smuggle a write in behind a query.
+    pub fn set_readonly_queries(&mut self, enabled: bool) {
+        self.readonly_queries = enabled;
+    }
+
     // -----------------------------------------------------------------------
     // File Import -- Uses DuckDB's native high-performance readers
     // -----------------------------------------------------------------------
@@ -107,6 +187,40 @@ impl DuckStorage {
         Ok(safe_name)
     }
 
+    /// Import a Hive-partitioned Parquet directory (e.g. `year=2024/month=01/*.parquet`)
+    /// into a persistent table. Partition keys in the directory names become columns.
+    /// Returns the sanitized table name used.
+    pub fn import_hive_partitioned(&self, root: &str, table_name: &str) -> Result<String> {
+        if !Path::new(root).is_dir() {
+            return Err(RustoraError::FileNotFound(root.to_string()));
+        }
+
+        let pattern = format!("{}/**/*.parquet", root.trim_end_matches(['/', '\\']));
+        let escaped_pattern = pattern.replace('\'', "''");
+        let matched: i64 = self
+            .conn
+            .query_row(
+                &format!("SELECT count(*) FROM glob('{}')", escaped_pattern),
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
+        if matched == 0 {
+            return Err(RustoraError::FileNotFound(pattern));
+        }
+
+        let safe_name = sanitize_table_name(table_name);
+        info!(root, table = %safe_name, files = matched, "importing Hive-partitioned Parquet");
+        let sql = format!(
+            "CREATE OR REPLACE TABLE \"{}\" AS SELECT * FROM read_parquet('{}', hive_partitioning=true)",
+            safe_name, escaped_pattern,
+        );
+        self.conn
+            .execute_batch(&sql)
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
+        Ok(safe_name)
+    }
+
     fn import_csv(&self, file_path: &str, table_name: &str) -> Result<()> {
         let escaped_path = file_path.replace('\'', "''");
         let sql = format!(
@@ -150,6 +264,11 @@ impl DuckStorage {
     /// Execute arbitrary SQL and stream the result directly as Arrow IPC bytes.
     /// Batches are written incrementally to avoid collecting the full result set in memory.
     pub fn query_to_ipc(&self, sql: &str) -> Result<Vec<u8>> {
+        if self.readonly_queries && !is_single_statement(sql) {
+            return Err(RustoraError::ReadOnly(
+                "only a single statement can run in read-only mode".to_string(),
+            ));
+        }
         info!(sql_len = sql.len(), "executing SQL query to IPC");
         let mut stmt = self
             .conn
@@ -168,6 +287,7 @@ impl DuckStorage {
 
         for batch in arrow_iter {
             if batch.num_rows() > 0 {
+                let batch = sanitize_utf8_batch(batch)?;
                 writer
                     .write(&batch)
                     .map_err(|e| RustoraError::DuckDb(format!("Arrow IPC write error: {}", e)))?;
@@ -181,6 +301,21 @@ impl DuckStorage {
         Ok(buffer)
     }
 
+    /// Like [`query_to_ipc`](Self::query_to_ipc), but only for a single SELECT statement.
+    /// Multi-statement text and statements that don't start with a query keyword
+    /// are rejected up front. The rest is handed to DuckDB's `query()` table
+    /// function as a string literal, which refuses non-SELECT statements at bind
+    /// time, so nothing but a query ever runs.
+    pub fn query_select_only_to_ipc(&self, sql: &str) -> Result<Vec<u8>> {
+        if !is_single_statement(sql) || !starts_with_query_keyword(sql) {
+            return Err(RustoraError::ReadOnly(
+                "only a single SELECT statement is allowed".to_string(),
+            ));
+        }
+        let wrapped = format!("SELECT * FROM query('{}')", sql.replace('\'', "''"));
+        self.query_to_ipc(&wrapped)
+    }
+
     /// Get a paginated chunk of a table as Arrow IPC bytes.
     pub fn get_table_chunk_ipc(
         &self,
@@ -200,6 +335,38 @@ impl DuckStorage {
         self.get_table_chunk_ipc(table_name, 0, limit)
     }
 
+    /// List the base tables a SQL statement reads, without executing it.
+    /// Uses DuckDB's JSON `EXPLAIN` output, so CTEs and subqueries resolve to the
+    /// tables they actually scan. Returns sorted, de-duplicated names.
+    /// Only a single statement is accepted: with more, DuckDB would run all but
+    /// the last while preparing the `EXPLAIN`.
+    pub fn referenced_tables(&self, sql: &str) -> Result<Vec<String>> {
+        if !is_single_statement(sql) {
+            return Err(RustoraError::Session(
+                "referenced_tables expects a single SQL statement".to_string(),
+            ));
+        }
+        let explain_sql = format!("EXPLAIN (FORMAT JSON) {}", sql);
+        let mut stmt = self
+            .conn
+            .prepare(&explain_sql)
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
+
+        let plans: Vec<String> = stmt
+            .query_map([], |row| row.get(1))
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
+
+        let mut tables = BTreeSet::new();
+        for plan in plans {
+            let value: serde_json::Value = serde_json::from_str(&plan)
+                .map_err(|e| RustoraError::DuckDb(format!("Unreadable query plan: {}", e)))?;
+            collect_scanned_tables(&value, &mut tables);
+        }
+        Ok(tables.into_iter().collect())
+    }
+
     // -----------------------------------------------------------------------
     // Table Management
     // -----------------------------------------------------------------------
@@ -222,6 +389,18 @@ impl DuckStorage {
         Ok(names)
     }
 
+    /// Check whether a single user table exists, without listing the whole catalog.
+    pub fn table_exists(&self, table_name: &str) -> Result<bool> {
+        self.conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM information_schema.tables \
+                 WHERE table_schema = 'main' AND table_name = ? AND table_name NOT LIKE '_rustora_%'",
+                [table_name],
+                |row| row.get(0),
+            )
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))
+    }
+
     /// Get detailed info about a specific table.
     pub fn table_info(&self, table_name: &str) -> Result<TableInfo> {
         let row_count = self.table_row_count(table_name)?;
@@ -305,6 +484,19 @@ impl DuckStorage {
         Ok(())
     }
 
+    /// Rename a table in place.
+    pub fn rename_table(&self, table_name: &str, new_name: &str) -> Result<()> {
+        let sql = format!(
+            "ALTER TABLE \"{}\" RENAME TO \"{}\"",
+            table_name.replace('"', "\"\""),
+            new_name.replace('"', "\"\"")
+        );
+        self.conn
+            .execute_batch(&sql)
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
+        Ok(())
+    }
+
     /// Execute a SQL statement that creates a result set and store it as a new table.
     /// Returns the table name.
     pub fn execute_sql_to_table(&self, sql: &str, result_table: &str) -> Result<String> {
@@ -328,28 +520,87 @@ impl DuckStorage {
         file_path: &str,
         table_name: &str,
         options: &CsvImportOptions,
-    ) -> Result<()> {
+    ) -> Result<String> {
+        let safe_name = sanitize_table_name(table_name);
+        // DuckDB appends to the reject tables on every scan; start from a clean slate
+        // so `csv_rejects` only reports this import.
+        self.conn
+            .execute_batch(
+                "DROP TABLE IF EXISTS temp._rustora_csv_rejects; \
+                 DROP TABLE IF EXISTS temp._rustora_csv_reject_scans;",
+            )
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
         let escaped_path = file_path.replace('\'', "''");
         let delim_char = options.delimiter as char;
         let header_str = if options.has_header { "true" } else { "false" };
         let skip = options.skip_rows;
         let sql = format!(
-            "CREATE OR REPLACE TABLE \"{}\" AS SELECT * FROM read_csv('{}', delim='{}', header={}, skip={})",
-            table_name, escaped_path, delim_char, header_str, skip
+            "CREATE OR REPLACE TABLE \"{}\" AS SELECT * FROM read_csv('{}', delim='{}', header={}, skip={}{}{})",
+            safe_name,
+            escaped_path,
+            delim_char,
+            header_str,
+            skip,
+            options.ragged_rows.read_csv_args(),
+            options.ragged_rows.reject_capture_args()
         );
         self.conn
             .execute_batch(&sql)
             .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
-        Ok(())
+        Ok(safe_name)
+    }
+
+    /// Lines dropped by the most recent [`import_csv_with_options`](Self::import_csv_with_options)
+    /// call, ordered by line. Empty unless that import used [`RaggedRowMode::SkipBadRows`].
+    pub fn csv_rejects(&self) -> Result<Vec<CsvRejectedRow>> {
+        let captured: bool = self
+            .conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM duckdb_tables() \
+                 WHERE temporary AND table_name = '_rustora_csv_rejects'",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
+        if !captured {
+            return Ok(Vec::new());
+        }
+
+        // A single line can produce several error entries; report it once.
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT line, csv_line, MIN(error_message) FROM temp._rustora_csv_rejects \
+                 GROUP BY line, csv_line ORDER BY line",
+            )
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(CsvRejectedRow {
+                    line: row.get(0)?,
+                    raw_line: row.get(1)?,
+                    error: row.get(2)?,
+                })
+            })
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
+        Ok(rows)
     }
 
     /// Preview a file's contents without importing. Returns Arrow IPC bytes.
+    /// `options` = None lets DuckDB sniff the CSV dialect. Type sniffing is capped at
+    /// `PREVIEW_SAMPLE_ROWS` (or `limit`, if larger) so huge files return quickly,
+    /// and `LIMIT` stops the scan once enough rows have been read.
     pub fn preview_file(
         &self,
         file_path: &str,
-        options: &CsvImportOptions,
+        options: Option<&CsvImportOptions>,
         limit: u64,
     ) -> Result<Vec<u8>> {
+        if !Path::new(file_path).exists() {
+            return Err(RustoraError::FileNotFound(file_path.to_string()));
+        }
         let escaped_path = file_path.replace('\'', "''");
         let ext = Path::new(file_path)
             .extension()
@@ -359,13 +610,28 @@ impl DuckStorage {
 
         let sql = match ext.as_str() {
             "csv" | "tsv" => {
-                let delim_char = options.delimiter as char;
-                let header_str = if options.has_header { "true" } else { "false" };
-                let skip = options.skip_rows;
-                format!(
-                    "SELECT * FROM read_csv('{}', delim='{}', header={}, skip={}) LIMIT {}",
-                    escaped_path, delim_char, header_str, skip, limit
-                )
+                let sample_size = limit.max(PREVIEW_SAMPLE_ROWS);
+                match options {
+                    Some(options) => {
+                        let delim_char = options.delimiter as char;
+                        let header_str = if options.has_header { "true" } else { "false" };
+                        let skip = options.skip_rows;
+                        format!(
+                            "SELECT * FROM read_csv('{}', delim='{}', header={}, skip={}, sample_size={}{}) LIMIT {}",
+                            escaped_path,
+                            delim_char,
+                            header_str,
+                            skip,
+                            sample_size,
+                            options.ragged_rows.read_csv_args(),
+                            limit
+                        )
+                    }
+                    None => format!(
+                        "SELECT * FROM read_csv('{}', auto_detect=true, sample_size={}) LIMIT {}",
+                        escaped_path, sample_size, limit
+                    ),
+                }
             }
             "parquet" | "pq" => {
                 format!(
@@ -409,6 +675,13 @@ impl DuckStorage {
         Ok(())
     }
 
+    pub fn delete_step_history(&self, table_name: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM _rustora_steps WHERE table_name = ?", [table_name])
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
+        Ok(())
+    }
+
     pub fn load_all_step_histories(&self) -> Result<Vec<(String, String)>> {
         let exists: bool = self
             .conn
@@ -444,10 +717,58 @@ impl DuckStorage {
 
     /// Export a table to CSV.
     pub fn export_to_csv(&self, table_name: &str, output_path: &str) -> Result<()> {
+        self.export_to_csv_with_options(table_name, output_path, &CsvExportOptions::default())
+    }
+
+    /// Export a table to CSV with number and null formatting.
+    /// Formatting is applied in the `COPY` projection only; the stored table is untouched.
+    pub fn export_to_csv_with_options(
+        &self,
+        table_name: &str,
+        output_path: &str,
+        options: &CsvExportOptions,
+    ) -> Result<()> {
+        ensure_parent_dir(output_path)?;
         let escaped = output_path.replace('\'', "''");
+
+        let source = match options.decimal_places {
+            Some(places) => {
+                let info = self.table_info(table_name)?;
+                let exprs: Vec<String> = info
+                    .column_names
+                    .iter()
+                    .zip(info.column_types.iter())
+                    .map(|(c, t)| {
+                        if let Some((precision, scale)) = decimal_precision_scale(t) {
+                            // Stay in DECIMAL so wide values don't lose digits to a DOUBLE.
+                            // One extra integer digit absorbs a carry (999.99 -> 1000.0).
+                            let places = places.min(38);
+                            let width =
+                                (precision - scale + places + 1).clamp(places.max(1), 38);
+                            format!(
+                                "CAST(ROUND(\"{}\", {}) AS DECIMAL({}, {})) AS \"{}\"",
+                                c, places, width, places, c
+                            )
+                        } else if is_float_type(t) {
+                            format!(
+                                "printf('%.{}f', CAST(\"{}\" AS DOUBLE)) AS \"{}\"",
+                                places, c, c
+                            )
+                        } else {
+                            format!("\"{}\"", c)
+                        }
+                    })
+                    .collect();
+                format!("(SELECT {} FROM \"{}\")", exprs.join(", "), table_name)
+            }
+            None => format!("\"{}\"", table_name),
+        };
+
         let sql = format!(
-            "COPY \"{}\" TO '{}' (FORMAT CSV, HEADER TRUE)",
-            table_name, escaped
+            "COPY {} TO '{}' (FORMAT CSV, HEADER TRUE, NULLSTR '{}')",
+            source,
+            escaped,
+            options.null_value.replace('\'', "''")
         );
         self.conn
             .execute_batch(&sql)
@@ -457,6 +778,7 @@ impl DuckStorage {
 
     /// Export a table to Parquet.
     pub fn export_to_parquet(&self, table_name: &str, output_path: &str) -> Result<()> {
+        ensure_parent_dir(output_path)?;
         let escaped = output_path.replace('\'', "''");
         let sql = format!(
             "COPY \"{}\" TO '{}' (FORMAT PARQUET)",
@@ -467,6 +789,83 @@ impl DuckStorage {
             .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
         Ok(())
     }
+
+    /// Stream a table to an Arrow IPC file on disk, `chunk_rows` rows at a time.
+    /// A single `SELECT *` is streamed from DuckDB and re-sliced into chunks of
+    /// `chunk_rows` rows; each chunk is written and dropped before the next is
+    /// filled, so memory use is bounded by the chunk size rather than the table size.
+    pub fn export_to_ipc_file(
+        &self,
+        table_name: &str,
+        output_path: &str,
+        chunk_rows: u64,
+    ) -> Result<()> {
+        let chunk_rows = chunk_rows.max(1) as usize;
+
+        let schema = {
+            let mut stmt = self
+                .conn
+                .prepare(&format!("SELECT * FROM \"{}\" LIMIT 0", table_name))
+                .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
+            let arrow_iter = stmt
+                .query_arrow([])
+                .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
+            arrow_iter.get_schema()
+        };
+
+        ensure_parent_dir(output_path)?;
+        let file = BufWriter::new(std::fs::File::create(output_path)?);
+        let mut writer = FileWriter::try_new(file, &schema)
+            .map_err(|e| RustoraError::DuckDb(format!("Arrow IPC write error: {}", e)))?;
+
+        let mut stmt = self
+            .conn
+            .prepare(&format!("SELECT * FROM \"{}\"", table_name))
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
+        let stream = stmt
+            .stream_arrow([], Arc::clone(&schema))
+            .map_err(|e| RustoraError::DuckDb(e.to_string()))?;
+
+        let mut total_rows: usize = 0;
+        let mut pending: Vec<RecordBatch> = Vec::new();
+        let mut pending_rows: usize = 0;
+        for batch in stream {
+            if batch.num_rows() == 0 {
+                continue;
+            }
+            pending_rows += batch.num_rows();
+            pending.push(batch);
+            if pending_rows < chunk_rows {
+                continue;
+            }
+
+            let combined = concat_batches(&schema, &pending)
+                .map_err(|e| RustoraError::DuckDb(format!("Arrow concat error: {}", e)))?;
+            pending.clear();
+            let mut offset = 0;
+            while combined.num_rows() - offset >= chunk_rows {
+                write_ipc_batch(&mut writer, combined.slice(offset, chunk_rows))?;
+                offset += chunk_rows;
+            }
+            pending_rows = combined.num_rows() - offset;
+            if pending_rows > 0 {
+                pending.push(combined.slice(offset, pending_rows));
+            }
+            total_rows += offset;
+        }
+        if pending_rows > 0 {
+            let rest = concat_batches(&schema, &pending)
+                .map_err(|e| RustoraError::DuckDb(format!("Arrow concat error: {}", e)))?;
+            write_ipc_batch(&mut writer, rest)?;
+            total_rows += pending_rows;
+        }
+
+        writer
+            .finish()
+            .map_err(|e| RustoraError::DuckDb(format!("Arrow IPC finish error: {}", e)))?;
+        info!(table = %table_name, output_path, rows = total_rows, "streamed table to IPC file");
+        Ok(())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -476,13 +875,217 @@ impl DuckStorage {
 /// Sanitize a string for use as a DuckDB table name.
 /// Replaces non-alphanumeric characters with underscores and truncates to 64 characters
 /// to prevent identifier length overflows in DuckDB.
-fn sanitize_table_name(name: &str) -> String {
+pub(crate) fn sanitize_table_name(name: &str) -> String {
     name.chars()
         .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
         .take(64)
         .collect()
 }
 
+/// Sanitize one chunk and append it to the IPC file.
+fn write_ipc_batch<W: std::io::Write>(
+    writer: &mut FileWriter<W>,
+    batch: RecordBatch,
+) -> Result<()> {
+    let batch = sanitize_utf8_batch(batch)?;
+    writer
+        .write(&batch)
+        .map_err(|e| RustoraError::DuckDb(format!("Arrow IPC write error: {}", e)))
+}
+
+/// Replace invalid UTF-8 in string columns with U+FFFD before a batch is serialized.
+///
+/// Batches arrive from DuckDB over the C data interface without UTF-8 validation,
+/// and arrow-js throws on malformed strings, so one bad value would otherwise
+/// break the whole grid. Valid columns are passed through untouched.
+fn sanitize_utf8_batch(batch: RecordBatch) -> Result<RecordBatch> {
+    let schema = batch.schema();
+    let mut columns: Vec<ArrayRef> = batch.columns().to_vec();
+    let mut changed = false;
+
+    for (field, column) in schema.fields().iter().zip(columns.iter_mut()) {
+        let repaired = match field.data_type() {
+            DataType::Utf8 => repair_utf8_column::<i32>(column),
+            DataType::LargeUtf8 => repair_utf8_column::<i64>(column),
+            _ => None,
+        };
+        if let Some(repaired) = repaired {
+            warn!(column = %field.name(), "replaced invalid UTF-8 in string column");
+            *column = repaired;
+            changed = true;
+        }
+    }
+
+    if !changed {
+        return Ok(batch);
+    }
+    RecordBatch::try_new(schema, columns)
+        .map_err(|e| RustoraError::DuckDb(format!("Arrow batch rebuild error: {}", e)))
+}
+
+/// Returns a lossily re-encoded copy of `column` if any value is not valid UTF-8.
+fn repair_utf8_column<O: OffsetSizeTrait>(column: &ArrayRef) -> Option<ArrayRef> {
+    if column.to_data().validate_values().is_ok() {
+        return None;
+    }
+    let strings = column.as_any().downcast_ref::<GenericStringArray<O>>()?;
+    let offsets = strings.value_offsets();
+    let bytes = strings.value_data();
+    let repaired: GenericStringArray<O> = (0..strings.len())
+        .map(|i| {
+            if strings.is_null(i) {
+                return None;
+            }
+            let value = &bytes[offsets[i].as_usize()..offsets[i + 1].as_usize()];
+            Some(String::from_utf8_lossy(value).into_owned())
+        })
+        .collect();
+    Some(Arc::new(repaired))
+}
+
+/// Walk a JSON query plan and collect every `extra_info.Table` entry, which DuckDB
+/// sets on scan operators (sequence scans, index scans, ...).
+fn collect_scanned_tables(node: &serde_json::Value, tables: &mut BTreeSet<String>) {
+    match node {
+        serde_json::Value::Object(map) => {
+            if let Some(table) = map
+                .get("extra_info")
+                .and_then(|info| info.get("Table"))
+                .and_then(|t| t.as_str())
+            {
+                tables.insert(table.to_string());
+            }
+            for child in map.values() {
+                collect_scanned_tables(child, tables);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_scanned_tables(item, tables);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether `sql` holds at most one statement (a trailing `;` is fine).
+///
+/// DuckDB's `prepare` runs every statement but the last, so user text must pass
+/// this before it reaches a path that is not supposed to execute anything.
+/// Semicolons inside quotes and comments are skipped. Backslash escapes, dollar
+/// quoting and nested comments are lexed differently by DuckDB, so when any of
+/// them shows up the check falls back to rejecting every non-trailing `;`.
+pub(crate) fn is_single_statement(sql: &str) -> bool {
+    let bytes = sql.as_bytes();
+    let mut i = 0;
+    let mut terminated = false;
+    let mut unsure = false;
+
+    while i < bytes.len() && !unsure {
+        match bytes[i] {
+            quote @ (b'\'' | b'"') => {
+                if terminated {
+                    return false;
+                }
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    unsure |= quote == b'\'' && bytes[i] == b'\\';
+                    i += 1;
+                }
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' && bytes[i] != b'\r' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i < bytes.len() && !bytes[i..].starts_with(b"*/") {
+                    unsure |= bytes[i..].starts_with(b"/*");
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'$' => unsure = true,
+            b';' => terminated = true,
+            b if b.is_ascii_whitespace() => {}
+            _ if terminated => return false,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if unsure {
+        let body = sql.trim_end_matches(|c: char| c.is_whitespace() || c == ';');
+        return !body.contains(';');
+    }
+    true
+}
+
+/// Whether a statement starts with a query keyword (`SELECT`, `WITH`, `FROM`,
+/// `VALUES`, `TABLE`), skipping leading whitespace, comments and parentheses.
+fn starts_with_query_keyword(sql: &str) -> bool {
+    let mut rest = sql;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '(');
+        if let Some(comment) = rest.strip_prefix("--") {
+            rest = comment.find(['\n', '\r']).map_or("", |i| &comment[i..]);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment.find("*/").map_or("", |i| &comment[i + 2..]);
+        } else {
+            break;
+        }
+    }
+    let keyword: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect::<String>()
+        .to_ascii_uppercase();
+    matches!(keyword.as_str(), "SELECT" | "WITH" | "FROM" | "VALUES" | "TABLE")
+}
+
+/// Whether a DuckDB column type is a binary floating-point type (DOUBLE, FLOAT, REAL).
+fn is_float_type(column_type: &str) -> bool {
+    let upper = column_type.to_uppercase();
+    upper == "DOUBLE" || upper == "FLOAT" || upper == "REAL"
+}
+
+/// Precision and scale of a `DECIMAL(p, s)` / `NUMERIC(p, s)` column type.
+/// A bare `DECIMAL` is DuckDB's default `DECIMAL(18, 3)`.
+fn decimal_precision_scale(column_type: &str) -> Option<(u32, u32)> {
+    let upper = column_type.to_uppercase();
+    let args = upper
+        .strip_prefix("DECIMAL")
+        .or_else(|| upper.strip_prefix("NUMERIC"))?
+        .trim();
+    if args.is_empty() {
+        return Some((18, 3));
+    }
+    let (precision, scale) = args.strip_prefix('(')?.strip_suffix(')')?.split_once(',')?;
+    let precision: u32 = precision.trim().parse().ok()?;
+    let scale: u32 = scale.trim().parse().ok()?;
+    (scale <= precision).then_some((precision, scale))
+}
+
+/// Create the parent directory of an export path if it does not exist yet.
+/// DuckDB's `COPY` and the Polars sinks both fail with opaque errors otherwise.
+pub(crate) fn ensure_parent_dir(output_path: &str) -> Result<()> {
+    if let Some(parent) = Path::new(output_path).parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                RustoraError::Io(std::io::Error::new(
+                    e.kind(),
+                    format!(
+                        "Cannot create export directory '{}': {}",
+                        parent.display(),
+                        e
+                    ),
+                ))
+            })?;
+        }
+    }
+    Ok(())
+}
 
 // ---------------------------------------------------------------------------
 // Tests
@@ -572,6 +1175,34 @@ mod tests {
         assert!(info.row_count > 0);
     }
 
+    #[test]
+    fn test_referenced_tables_rejects_multiple_statements() {
+        let storage = DuckStorage::open_in_memory().unwrap();
+        storage.execute_sql_to_table("SELECT 1 AS x", "t").unwrap();
+
+        assert!(storage.referenced_tables("SELECT 1; DROP TABLE t").is_err());
+        assert!(storage.table_exists("t").unwrap());
+
+        assert_eq!(
+            storage.referenced_tables("SELECT * FROM t;").unwrap(),
+            vec!["t".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_is_single_statement() {
+        assert!(is_single_statement("SELECT 1"));
+        assert!(is_single_statement("SELECT 1; -- done\n"));
+        assert!(is_single_statement("SELECT ';' AS \"a;b\" /* ; */"));
+        assert!(is_single_statement("SELECT 'C:\\temp'"));
+        assert!(!is_single_statement("SELECT 1; DROP TABLE t"));
+        assert!(!is_single_statement("SELECT 'it''s'; DROP TABLE t"));
+        // Lexed differently by DuckDB, so any inner `;` is rejected.
+        assert!(!is_single_statement("SELECT E'\\''; DROP TABLE t; --'"));
+        assert!(!is_single_statement("SELECT $$ ' $$; DROP TABLE t; --'"));
+        assert!(!is_single_statement("SELECT 1 /* /* */ ' */; DROP TABLE t; --'"));
+    }
+
     #[test]
     fn test_drop_table() {
         let csv = create_test_csv();
@@ -604,6 +1235,188 @@ mod tests {
         assert!(content.contains("Alice"));
     }
 
+    #[test]
+    fn test_export_csv_with_decimal_places_and_null() {
+        let mut csv = NamedTempFile::with_suffix(".csv").unwrap();
+        writeln!(csv, "label,value").unwrap();
+        writeln!(csv, "a,95.50000000001").unwrap();
+        writeln!(csv, "b,").unwrap();
+        writeln!(csv, "c,3.14159").unwrap();
+        let csv_path = csv.path().to_str().unwrap();
+
+        let storage = DuckStorage::open_in_memory().unwrap();
+        storage.import_file(csv_path, "fmt_export").unwrap();
+
+        let out = NamedTempFile::with_suffix(".csv").unwrap();
+        let out_path = out.path().to_str().unwrap();
+        let options = CsvExportOptions {
+            decimal_places: Some(2),
+            null_value: "NULL".to_string(),
+        };
+        storage
+            .export_to_csv_with_options("fmt_export", out_path, &options)
+            .unwrap();
+
+        let content = std::fs::read_to_string(out_path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines, vec!["label,value", "a,95.50", "b,NULL", "c,3.14"]);
+
+        // The stored data keeps full precision.
+        storage.export_to_csv("fmt_export", out_path).unwrap();
+        let raw = std::fs::read_to_string(out_path).unwrap();
+        assert!(raw.contains("95.50000000001"));
+
+        // DECIMAL columns are rounded as decimals, not through a DOUBLE.
+        storage
+            .execute_sql_to_table(
+                "SELECT CAST('1234567890123456.785' AS DECIMAL(38, 3)) AS amount \
+                 UNION ALL SELECT NULL",
+                "fmt_decimal",
+            )
+            .unwrap();
+        storage
+            .export_to_csv_with_options("fmt_decimal", out_path, &options)
+            .unwrap();
+        let content = std::fs::read_to_string(out_path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines, vec!["amount", "1234567890123456.79", "NULL"]);
+
+        // Rounding may carry into a new integer digit.
+        storage
+            .execute_sql_to_table(
+                "SELECT CAST('999.99' AS DECIMAL(5, 2)) AS amount",
+                "fmt_carry",
+            )
+            .unwrap();
+        let options = CsvExportOptions {
+            decimal_places: Some(1),
+            null_value: "NULL".to_string(),
+        };
+        storage
+            .export_to_csv_with_options("fmt_carry", out_path, &options)
+            .unwrap();
+        let content = std::fs::read_to_string(out_path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines, vec!["amount", "1000.0"]);
+    }
+
+    #[test]
+    fn test_export_ipc_file_chunked() {
+        let csv = create_test_csv();
+        let csv_path = csv.path().to_str().unwrap();
+
+        let storage = DuckStorage::open_in_memory().unwrap();
+        storage.import_file(csv_path, "ipc_export").unwrap();
+
+        let out = NamedTempFile::with_suffix(".arrow").unwrap();
+        let out_path = out.path().to_str().unwrap();
+
+        // A chunk size smaller than the table splits the output into several batches.
+        storage.export_to_ipc_file("ipc_export", out_path, 2).unwrap();
+
+        let file = std::fs::File::open(out_path).unwrap();
+        let reader = arrow_ipc::reader::FileReader::try_new(file, None).unwrap();
+        assert_eq!(reader.schema().fields().len(), 4);
+        let sizes: Vec<usize> = reader.map(|b| b.unwrap().num_rows()).collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn test_preview_file_missing() {
+        let storage = DuckStorage::open_in_memory().unwrap();
+        let result = storage.preview_file("does_not_exist.csv", None, 10);
+        assert!(matches!(result, Err(RustoraError::FileNotFound(_))));
+    }
+
+    #[test]
+    fn test_sanitize_utf8_batch_replaces_invalid_bytes() {
+        use duckdb::arrow::array::{Int32Array, StringArray};
+        use duckdb::arrow::buffer::{Buffer, OffsetBuffer};
+        use duckdb::arrow::datatypes::{Field, Schema};
+
+        // "ok", NULL, "b\xFFd" -- the last value is not valid UTF-8.
+        let values = Buffer::from(b"okb\xFFd".to_vec());
+        let offsets = OffsetBuffer::new(vec![0i32, 2, 2, 5].into());
+        let nulls = Some(vec![true, false, true].into());
+        let bad = unsafe { StringArray::new_unchecked(offsets, values, nulls) };
+        assert!(bad.to_data().validate_values().is_err());
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("label", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int32Array::from(vec![1, 2, 3])), Arc::new(bad)],
+        )
+        .unwrap();
+
+        let clean = sanitize_utf8_batch(batch).unwrap();
+        let labels = clean
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(labels.value(0), "ok");
+        assert!(labels.is_null(1));
+        assert_eq!(labels.value(2), "b\u{FFFD}d");
+
+        // The repaired batch must now serialize and read back as valid IPC.
+        let mut buffer = Vec::new();
+        let mut writer = StreamWriter::try_new(&mut buffer, &clean.schema()).unwrap();
+        writer.write(&clean).unwrap();
+        writer.finish().unwrap();
+        assert!(!buffer.is_empty());
+    }
+
+    #[test]
+    fn test_import_ragged_csv_modes() {
+        let mut csv = NamedTempFile::with_suffix(".csv").unwrap();
+        writeln!(csv, "a,b,c").unwrap();
+        writeln!(csv, "1,2,3").unwrap();
+        writeln!(csv, "4,5").unwrap();
+        writeln!(csv, "6,7,8").unwrap();
+        writeln!(csv, "9").unwrap();
+        writeln!(csv, "10,11,12").unwrap();
+        let csv_path = csv.path().to_str().unwrap();
+
+        let storage = DuckStorage::open_in_memory().unwrap();
+        let mut options = CsvImportOptions::default();
+
+        assert!(storage
+            .import_csv_with_options(csv_path, "ragged_strict", &options)
+            .is_err());
+
+        options.ragged_rows = RaggedRowMode::SkipBadRows;
+        storage
+            .import_csv_with_options(csv_path, "ragged_skip", &options)
+            .unwrap();
+        assert_eq!(storage.table_row_count("ragged_skip").unwrap(), 3);
+        let rejects = storage.csv_rejects().unwrap();
+        let lines: Vec<(u64, &str)> = rejects
+            .iter()
+            .map(|r| (r.line, r.raw_line.as_str()))
+            .collect();
+        assert_eq!(lines, vec![(3, "4,5"), (5, "9")]);
+
+        // A preview in the same mode skips bad rows without touching the import's rejects.
+        let mut other = NamedTempFile::with_suffix(".csv").unwrap();
+        writeln!(other, "a,b,c").unwrap();
+        writeln!(other, "1").unwrap();
+        let other_path = other.path().to_str().unwrap();
+        assert!(!storage.preview_file(other_path, Some(&options), 10).unwrap().is_empty());
+        assert_eq!(storage.csv_rejects().unwrap(), rejects);
+
+        options.ragged_rows = RaggedRowMode::NullPad;
+        storage
+            .import_csv_with_options(csv_path, "ragged_pad", &options)
+            .unwrap();
+        assert_eq!(storage.table_row_count("ragged_pad").unwrap(), 5);
+        let info = storage.table_info("ragged_pad").unwrap();
+        assert_eq!(info.num_columns, 3);
+        assert!(storage.csv_rejects().unwrap().is_empty());
+    }
+
     #[test]
     fn test_persistent_storage() {
         let dir = tempfile::tempdir().unwrap();
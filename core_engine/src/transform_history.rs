@@ -11,10 +11,13 @@ pub enum TransformStep {
     KeepColumns { columns: Vec<String> },
     ChangeType { column: String, new_type: String },
     RenameColumn { old_name: String, new_name: String },
+    RenameColumns { mapping: Vec<(String, String)> },
+    AddRowNumber { column: String, order_by: Vec<String> },
     Pivot { index_cols: Vec<String>, pivot_col: String, value_col: String, agg: String },
     Unpivot { id_cols: Vec<String>, value_cols: Vec<String>, var_name: String, value_name: String },
     Merge { right_table: String, left_col: String, right_col: String, join_type: String },
     Append { tables: Vec<String> },
+    HStack { right_table: String, suffix: Option<String> },
     Sql { query: String },
 }
 
@@ -46,10 +49,18 @@ impl TransformStep {
             Self::KeepColumns { columns } => format!("Kept: {}", columns.join(", ")),
             Self::ChangeType { column, new_type } => format!("Type: {} -> {}", column, new_type),
             Self::RenameColumn { old_name, new_name } => format!("Renamed: {} -> {}", old_name, new_name),
+            Self::RenameColumns { mapping } => {
+                let pairs: Vec<String> = mapping.iter()
+                    .map(|(old, new)| format!("{} -> {}", old, new))
+                    .collect();
+                format!("Renamed: {}", pairs.join(", "))
+            }
+            Self::AddRowNumber { column, .. } => format!("Row number: {}", column),
             Self::Pivot { pivot_col, .. } => format!("Pivot on: {}", pivot_col),
             Self::Unpivot { value_cols, .. } => format!("Unpivot: {}", value_cols.join(", ")),
             Self::Merge { right_table, join_type, .. } => format!("Merge: {} ({})", right_table, join_type),
             Self::Append { tables } => format!("Append: {}", tables.join(", ")),
+            Self::HStack { right_table, .. } => format!("Stacked columns: {}", right_table),
             Self::Sql { query } => {
                 let s = if query.len() > 40 { &query[..40] } else { query };
                 format!("SQL: {}", s)
@@ -424,6 +424,8 @@ pub fn parse_filter_operator(op: &str) -> Result<FilterOperator, String> {
     match op {
         "equals" => Ok(FilterOperator::Equals),
         "not_equals" => Ok(FilterOperator::NotEquals),
+        "null_safe_equals" => Ok(FilterOperator::NullSafeEquals),
+        "null_safe_not_equals" => Ok(FilterOperator::NullSafeNotEquals),
         "greater_than" => Ok(FilterOperator::GreaterThan),
         "greater_than_or_equal" => Ok(FilterOperator::GreaterThanOrEqual),
         "less_than" => Ok(FilterOperator::LessThan),
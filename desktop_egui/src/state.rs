@@ -298,11 +298,11 @@ impl AppState {
 
             for row_idx in 0..num_rows {
                 let mut row = Vec::with_capacity(num_cols);
-                for col_idx in 0..num_cols {
-                    let val = if string_cols[col_idx].is_null(row_idx) {
+                for string_col in &string_cols {
+                    let val = if string_col.is_null(row_idx) {
                         String::new()
                     } else {
-                        string_cols[col_idx].value(row_idx).to_string()
+                        string_col.value(row_idx).to_string()
                     };
                     row.push(val);
                 }
@@ -205,16 +205,15 @@ pub fn show_data_grid(ui: &mut Ui, state: &mut AppState) {
 
     // Pagination + Go to row
     ui.horizontal(|ui| {
-        if !state.loading && current_offset > 0 {
-            if ui.button("<< Previous").clicked() {
-                state.load_page(current_offset.saturating_sub(page_size));
-            }
+        if !state.loading && current_offset > 0 && ui.button("<< Previous").clicked() {
+            state.load_page(current_offset.saturating_sub(page_size));
         }
-        if !state.loading && num_rows > 0 && current_offset + page_size < total_rows {
-            if ui.button("Next >>").clicked() {
-                state
-                    .load_page((current_offset + page_size).min(total_rows.saturating_sub(1)));
-            }
+        if !state.loading
+            && num_rows > 0
+            && current_offset + page_size < total_rows
+            && ui.button("Next >>").clicked()
+        {
+            state.load_page((current_offset + page_size).min(total_rows.saturating_sub(1)));
         }
 
         ui.separator();
@@ -94,13 +94,12 @@ pub fn show_menu_bar(ui: &mut Ui, state: &mut AppState) {
             state.calc_dialog = true;
         }
 
-        if state.features.column_ops {
-            if ui
+        if state.features.column_ops
+            && ui
                 .add_enabled(state.name.is_some(), egui::Button::new("Rename Col"))
                 .clicked()
-            {
-                state.rename_dialog = true;
-            }
+        {
+            state.rename_dialog = true;
         }
 
         if ui
@@ -162,7 +161,7 @@ pub fn show_menu_bar(ui: &mut Ui, state: &mut AppState) {
         {
             state.chart_visible = !state.chart_visible;
             if state.chart_visible {
-                if let Some(ref col) = state.columns.first() {
+                if let Some(col) = state.columns.first() {
                     if state.chart_group_col.is_empty() {
                         state.chart_group_col = col.name.clone();
                     }
@@ -1,9 +1,11 @@
 use core_engine::{
-    FilterCondition, FilterLogic, FilterOperator, FilterSpec, RustoraError, RustoraSession,
+    DatasetEvent, FilterCondition, FilterLogic, FilterOperator, FilterSpec, FilterValidationIssue,
+    RustoraError, RustoraSession,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
-use tauri::State;
+use tauri::{Emitter, State};
 
 /// Thread-safe wrapper around the core engine session.
 /// Uses Arc so the mutex can be cloned into async spawn_blocking tasks
@@ -46,6 +48,7 @@ impl From<RustoraError> for CommandError {
             RustoraError::InvalidEdit(_) => ("invalid_edit", "data"),
             RustoraError::NoProjectOpen => ("no_project_open", "session"),
             RustoraError::Session(_) => ("session_error", "session"),
+            RustoraError::ReadOnly(_) => ("read_only", "session"),
         };
         Self {
             code: code.to_string(),
@@ -222,6 +225,24 @@ async fn get_chunk(
     .map_err(|e| CommandError::internal(e.to_string()))?
 }
 
+/// Fetch several chunks in one round trip, keyed by offset (used for scroll-ahead).
+/// At most `core_engine::session::MAX_PREFETCH_CHUNKS` offsets per call.
+#[tauri::command]
+async fn prefetch_chunks(
+    state: State<'_, AppState>,
+    dataset_name: String,
+    offsets: Vec<u32>,
+    limit: u32,
+) -> Result<BTreeMap<u32, Vec<u8>>, CommandError> {
+    let session = state.session.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let session = session.lock().map_err(|e| CommandError::internal(e.to_string()))?;
+        Ok(session.prefetch_chunks(&dataset_name, &offsets, limit)?)
+    })
+    .await
+    .map_err(|e| CommandError::internal(e.to_string()))?
+}
+
 /// Sort a dataset and return new dataset metadata.
 #[tauri::command]
 async fn sort_dataset(
@@ -336,6 +357,8 @@ fn parse_operator(op: &str) -> Result<FilterOperator, CommandError> {
     match op {
         "equals" => Ok(FilterOperator::Equals),
         "not_equals" => Ok(FilterOperator::NotEquals),
+        "null_safe_equals" => Ok(FilterOperator::NullSafeEquals),
+        "null_safe_not_equals" => Ok(FilterOperator::NullSafeNotEquals),
         "greater_than" => Ok(FilterOperator::GreaterThan),
         "greater_than_or_equal" => Ok(FilterOperator::GreaterThanOrEqual),
         "less_than" => Ok(FilterOperator::LessThan),
@@ -354,14 +377,10 @@ fn parse_operator(op: &str) -> Result<FilterOperator, CommandError> {
     }
 }
 
-/// Filter a dataset using structured conditions (safe from SQL injection).
-#[tauri::command]
-async fn filter_dataset_structured(
-    state: State<'_, AppState>,
-    dataset_name: String,
+fn parse_filter_spec(
     conditions: Vec<FilterConditionInput>,
-    logic: String,
-) -> Result<OpenResult, CommandError> {
+    logic: &str,
+) -> Result<FilterSpec, CommandError> {
     let parsed_conditions: Vec<FilterCondition> = conditions
         .into_iter()
         .map(|c| {
@@ -373,15 +392,26 @@ async fn filter_dataset_structured(
         })
         .collect::<Result<Vec<_>, CommandError>>()?;
 
-    let filter_logic = match logic.as_str() {
+    let filter_logic = match logic {
         "or" => FilterLogic::Or,
         _ => FilterLogic::And,
     };
 
-    let spec = FilterSpec {
+    Ok(FilterSpec {
         conditions: parsed_conditions,
         logic: filter_logic,
-    };
+    })
+}
+
+/// Filter a dataset using structured conditions (safe from SQL injection).
+#[tauri::command]
+async fn filter_dataset_structured(
+    state: State<'_, AppState>,
+    dataset_name: String,
+    conditions: Vec<FilterConditionInput>,
+    logic: String,
+) -> Result<OpenResult, CommandError> {
+    let spec = parse_filter_spec(conditions, &logic)?;
 
     let session = state.session.clone();
     tauri::async_runtime::spawn_blocking(move || {
@@ -393,6 +423,26 @@ async fn filter_dataset_structured(
     .map_err(|e| CommandError::internal(e.to_string()))?
 }
 
+/// Validate structured filter conditions against a dataset without running them.
+/// Used by the filter builder for inline form validation.
+#[tauri::command]
+async fn validate_filter(
+    state: State<'_, AppState>,
+    dataset_name: String,
+    conditions: Vec<FilterConditionInput>,
+    logic: String,
+) -> Result<Vec<FilterValidationIssue>, CommandError> {
+    let spec = parse_filter_spec(conditions, &logic)?;
+
+    let session = state.session.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let session = session.lock().map_err(|e| CommandError::internal(e.to_string()))?;
+        Ok(session.validate_filter(&dataset_name, &spec)?)
+    })
+    .await
+    .map_err(|e| CommandError::internal(e.to_string()))?
+}
+
 /// Group a dataset by columns with aggregate expressions.
 #[tauri::command]
 async fn group_by(
@@ -477,11 +527,23 @@ async fn get_summary_stats(
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let session = Arc::new(Mutex::new(RustoraSession::new()));
+    let observed = Arc::clone(&session);
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
-        .manage(AppState {
-            session: Arc::new(Mutex::new(RustoraSession::new())),
+        .manage(AppState { session })
+        .setup(move |app| {
+            // Bridge dataset changes into a window event so the sidebar can
+            // refresh on demand instead of re-listing after every command.
+            let handle = app.handle().clone();
+            if let Ok(mut session) = observed.lock() {
+                session.on_change(move |event: &DatasetEvent| {
+                    let _ = handle.emit("dataset-changed", event);
+                });
+            }
+            Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             new_project,
@@ -490,6 +552,7 @@ pub fn run() {
             import_file,
             open_file,
             get_chunk,
+            prefetch_chunks,
             sort_dataset,
             execute_sql,
             export_dataset,
@@ -497,6 +560,7 @@ pub fn run() {
             remove_dataset,
             filter_dataset,
             filter_dataset_structured,
+            validate_filter,
             group_by,
             add_calculated_column,
             aggregate_for_chart,
@@ -1,5 +1,6 @@
-import { useState, useCallback, useRef } from "react";
+import { useState, useCallback, useEffect, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { parseIpcBytes, type ParsedTable } from "../lib/arrow";
 import { errorMessage } from "../lib/error";
 
@@ -92,6 +93,17 @@ export function useDataset() {
     }
   }, []);
 
+  // The backend emits `dataset-changed` whenever a dataset is created, dropped,
+  // renamed, or overwritten, so the sidebar stays in sync without polling.
+  useEffect(() => {
+    const unlisten = listen("dataset-changed", () => {
+      void refreshTableList();
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [refreshTableList]);
+
   /** Re-invoke the most recently failed action with the same arguments. */
   const retryLastAction = useCallback(async () => {
     if (retryRef.current) {
@@ -1,6 +1,6 @@
 """Type stubs for the rustora native extension module."""
 
-from typing import Optional
+from typing import Any, Optional
 
 class Session:
     """Core session managing all data operations.
@@ -212,6 +212,64 @@ class Session:
         """
         ...
 
+    def stream_to_ipc(self, name: str, output_path: str) -> None:
+        """Stream a dataset to an Arrow IPC file without loading it into memory.
+
+        Rows are fetched and written in fixed-size chunks, so this is safe for
+        tables far larger than RAM. Read the result with ``pyarrow.ipc.open_file``
+        or ``polars.read_ipc``.
+
+        Args:
+            name: Dataset / table name.
+            output_path: Destination file path.
+
+        Raises:
+            ValueError: If the dataset is not found.
+            IOError: If the output file cannot be created.
+        """
+        ...
+
+    def group_aggregate(
+        self,
+        name: str,
+        group_cols: list[str],
+        aggs: list[tuple[str, Optional[str], str]],
+    ) -> list[dict[str, Any]]:
+        """Group a table and compute aggregates, returning typed Python values.
+
+        Unlike building SQL strings, columns are checked against the table schema
+        and functions are limited to ``count``, ``count_distinct``, ``sum``,
+        ``avg``, ``min`` and ``max``.
+
+        Args:
+            name: Table name.
+            group_cols: Columns to group by.
+            aggs: ``(function, column, alias)`` tuples. ``column`` may be
+                ``None`` only for ``"count"`` (counts rows).
+
+        Returns:
+            One dict per group, ordered by the group columns, keyed by the group
+            column names followed by the aliases.
+
+        Raises:
+            ValueError: If the table or a column is not found.
+            RuntimeError: If a function is not supported or an alias is invalid.
+        """
+        ...
+
+    def set_readonly_queries(self, enabled: bool) -> None:
+        """Restrict the session to read-only queries.
+
+        While enabled, ``query_to_ipc`` accepts only single SELECT-style
+        statements, and previews, chunks and row counts keep working. Imports,
+        exports, transforms, ``execute_sql`` and ``remove_dataset`` raise
+        ``PermissionError``. The project file itself is not reopened read-only.
+
+        Args:
+            enabled: True to enforce the policy, False to lift it.
+        """
+        ...
+
     def remove_dataset(self, name: str) -> bool:
         """Remove a dataset (drops DuckDB table or removes transient scan).
 
@@ -1,7 +1,9 @@
-use core_engine::RustoraSession;
-use pyo3::exceptions::{PyFileNotFoundError, PyIOError, PyRuntimeError, PyValueError};
+use core_engine::{AggFunction, AggSpec, GroupValue, RustoraSession};
+use pyo3::exceptions::{
+    PyFileNotFoundError, PyIOError, PyPermissionError, PyRuntimeError, PyValueError,
+};
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::types::{PyBytes, PyDict};
 
 /// Python wrapper for the Rustora core engine session.
 ///
@@ -148,6 +150,60 @@ impl Session {
             .map_err(map_err)
     }
 
+    /// Stream a dataset to an Arrow IPC file on disk in bounded memory.
+    fn stream_to_ipc(&self, name: &str, output_path: &str) -> PyResult<()> {
+        self.inner
+            .export_to_ipc_file(name, output_path)
+            .map_err(map_err)
+    }
+
+    /// Group a table and compute aggregates, returning one dict per group.
+    /// `aggs` is a list of `(function, column, alias)` tuples; `column` may be
+    /// None for "count". Dict keys are the group columns followed by the aliases.
+    fn group_aggregate<'py>(
+        &self,
+        py: Python<'py>,
+        name: &str,
+        group_cols: Vec<String>,
+        aggs: Vec<(String, Option<String>, String)>,
+    ) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let specs = aggs
+            .into_iter()
+            .map(|(function, column, alias)| {
+                Ok(AggSpec {
+                    function: AggFunction::parse(&function)?,
+                    column,
+                    alias,
+                })
+            })
+            .collect::<core_engine::Result<Vec<_>>>()
+            .map_err(map_err)?;
+        let group_refs: Vec<&str> = group_cols.iter().map(|s| s.as_str()).collect();
+        let rows = self
+            .inner
+            .group_aggregate(name, &group_refs, &specs)
+            .map_err(map_err)?;
+
+        rows.iter()
+            .map(|row| {
+                let dict = PyDict::new(py);
+                for (col, value) in group_cols.iter().zip(&row.keys) {
+                    dict.set_item(col, group_value_to_py(py, value)?)?;
+                }
+                for (spec, value) in specs.iter().zip(&row.values) {
+                    dict.set_item(&spec.alias, group_value_to_py(py, value)?)?;
+                }
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    /// Allow only read paths (previews, chunks, SELECT via query_to_ipc).
+    /// Imports, exports, transforms and execute_sql raise PermissionError.
+    fn set_readonly_queries(&mut self, enabled: bool) {
+        self.inner.set_readonly_queries(enabled)
+    }
+
     /// Remove a dataset.
     fn remove_dataset(&mut self, name: &str) -> PyResult<bool> {
         self.inner
@@ -156,6 +212,17 @@ impl Session {
     }
 }
 
+/// Convert a typed aggregate cell into the matching Python scalar (or None).
+fn group_value_to_py(py: Python<'_>, value: &GroupValue) -> PyResult<PyObject> {
+    Ok(match value {
+        GroupValue::Null => py.None(),
+        GroupValue::Bool(b) => b.into_pyobject(py)?.to_owned().into_any().unbind(),
+        GroupValue::Int(i) => i.into_pyobject(py)?.into_any().unbind(),
+        GroupValue::Float(f) => f.into_pyobject(py)?.into_any().unbind(),
+        GroupValue::Text(s) => s.into_pyobject(py)?.into_any().unbind(),
+    })
+}
+
 /// Map a [`core_engine::error::RustoraError`] to the most appropriate Python exception type.
 /// Provides richer error semantics than mapping everything to `RuntimeError`.
 fn map_err(e: core_engine::RustoraError) -> pyo3::PyErr {
@@ -167,6 +234,7 @@ fn map_err(e: core_engine::RustoraError) -> pyo3::PyErr {
         | RustoraError::InvalidEdit(_)
         | RustoraError::TableNotFound(_) => PyValueError::new_err(e.to_string()),
         RustoraError::Io(_) => PyIOError::new_err(e.to_string()),
+        RustoraError::ReadOnly(_) => PyPermissionError::new_err(e.to_string()),
         _ => PyRuntimeError::new_err(e.to_string()),
     }
 }
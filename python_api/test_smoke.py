@@ -125,6 +125,27 @@ def main():
         assert os.path.getsize(out_parquet) > 0
         print(f"[OK] Exported Parquet: {os.path.getsize(out_parquet)} bytes")
 
+        # ── stream_to_ipc (chunked, bounded memory) ──────────────────────
+        out_ipc = os.path.join(tmpdir, "out.arrow")
+        session.stream_to_ipc("test_data", out_ipc)
+        assert os.path.getsize(out_ipc) > 0
+        try:
+            import pyarrow.ipc as pa_ipc
+            streamed = pa_ipc.open_file(out_ipc).read_all()
+            assert streamed.num_rows == 3, f"Expected 3 rows, got {streamed.num_rows}"
+            assert streamed.column_names == ["name", "age", "city", "score"]
+            print(f"[OK] stream_to_ipc: {streamed.num_rows} rows read back via pyarrow")
+        except ImportError:
+            print("[SKIP] pyarrow not installed, skipping stream_to_ipc read-back")
+
+        # ── group_aggregate (typed results) ───────────────────────────────
+        groups = session.group_aggregate(
+            "test_data", ["city"], [("count", None, "n"), ("avg", "score", "avg_score")]
+        )
+        assert len(groups) == 3
+        assert groups[0] == {"city": "Chicago", "n": 1, "avg_score": 72.3}
+        print(f"[OK] group_aggregate: {groups}")
+
         # ── remove_dataset ────────────────────────────────────────────────
         removed = session.remove_dataset("test_data")
         assert removed is True